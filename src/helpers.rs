@@ -28,5 +28,2220 @@ where
     V: Clone,
     I: std::iter::IntoIterator<Item = (K, V)>,
 {
-    return iter.into_iter().collect();
+    iter.into_iter().collect()
+}
+
+/// Replaces `{key}` placeholders in a template with their matching values.
+///
+/// This function does the substitution for the `s!(tpl ...)` arm. Each
+/// `{key}` in the template is swapped for the value paired with `key`.
+/// Placeholders without a matching key are left in the output as-is,
+/// braces included.
+///
+/// # Arguments
+///
+/// * `template` - The text containing `{key}` placeholders.
+/// * `values` - The key-value pairs used for substitution.
+///
+/// # Returns
+///
+/// A `String` with every matched placeholder replaced.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::substitute;
+///
+/// let values = [("name", String::from("World"))];
+/// assert_eq!(substitute("Hello {name}", &values), "Hello World");
+/// assert_eq!(substitute("Hello {other}", &values), "Hello {other}");
+/// ```
+pub fn substitute(template: &str, values: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = match after.find(['{', '}']) {
+            Some(end) if after[end..].starts_with('}') => end,
+            _ => {
+                // Not a complete placeholder, keep the brace and move on
+                output.push('{');
+                rest = after;
+                continue;
+            }
+        };
+        let key = &after[..end];
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => output.push_str(value),
+            None => output.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Removes elements whose derived key has already been seen.
+///
+/// This function backs the `vec_dedup_by_key!` macro. It walks the items in
+/// order, keeping the first element for every distinct key and dropping the rest.
+///
+/// # Arguments
+///
+/// * `iter` - An iterator over the elements to deduplicate.
+/// * `key` - A closure deriving the key used to compare elements.
+///
+/// # Returns
+///
+/// A `Vec` of the first element for each key, in their original order.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::dedup_by_key;
+///
+/// let words = vec!["apple", "avocado", "banana", "blueberry", "cherry"];
+/// let firsts = dedup_by_key(words, |word| word.chars().next());
+/// assert_eq!(firsts, vec!["apple", "banana", "cherry"]);
+/// ```
+pub fn dedup_by_key<T, K, I, F>(iter: I, mut key: F) -> Vec<T>
+where
+    K: std::hash::Hash + Eq,
+    I: std::iter::IntoIterator<Item = T>,
+    F: FnMut(&T) -> K,
+{
+    let mut seen = std::collections::HashSet::new();
+    iter.into_iter().filter(|item| seen.insert(key(item))).collect()
+}
+
+/// Formats a `Duration` as an ISO-8601 duration string.
+///
+/// This function backs the `s!(iso_duration; ...)` arm. Hours, minutes and
+/// seconds are emitted only when non-zero, with sub-second precision kept as a
+/// trimmed decimal fraction on the seconds. A zero duration becomes `"PT0S"`.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to format.
+///
+/// # Returns
+///
+/// A `String` such as `"PT1H2M3S"` or `"PT0.5S"`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::iso_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(iso_duration(Duration::from_secs(3723)), "PT1H2M3S");
+/// assert_eq!(iso_duration(Duration::from_millis(1500)), "PT1.5S");
+/// ```
+pub fn iso_duration(duration: std::time::Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, total % 3600 / 60, total % 60);
+    let nanos = duration.subsec_nanos();
+
+    let mut iso = String::from("PT");
+    if hours > 0 {
+        iso.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        iso.push_str(&format!("{}M", minutes));
+    }
+    if seconds > 0 || nanos > 0 || (hours == 0 && minutes == 0) {
+        iso.push_str(&seconds.to_string());
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            iso.push('.');
+            iso.push_str(fraction.trim_end_matches('0'));
+        }
+        iso.push('S');
+    }
+    iso
+}
+
+/// Transforms every value of a HashMap, keeping the keys as they are.
+///
+/// This function is used by the `chained_map!` macro for its `map_values`
+/// stage, consuming the map and rebuilding it with the transformed values.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap whose values are transformed.
+/// * `transform` - A closure mapping each value to its new value.
+///
+/// # Returns
+///
+/// A HashMap with the same keys and the transformed values.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::map_values;
+///
+/// let prices = map_values(vec![("apple", 2), ("pear", 3)].into_iter().collect(), |v| v * 100);
+/// assert_eq!(prices.get("apple"), Some(&200));
+/// assert_eq!(prices.get("pear"), Some(&300));
+/// ```
+pub fn map_values<K, V, W, F>(map: std::collections::HashMap<K, V>, mut transform: F) -> std::collections::HashMap<K, W>
+where
+    K: std::hash::Hash + Eq,
+    F: FnMut(V) -> W,
+{
+    map.into_iter().map(|(key, value)| (key, transform(value))).collect()
+}
+
+/// Where the `…` marker goes when text is shortened by [`ellipsize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Keep the end of the text: `"…name.txt"`
+    Start,
+    /// Keep both ends of the text: `"very…txt"`
+    Mid,
+    /// Keep the start of the text: `"verylong…"`
+    End,
+}
+
+/// Shortens text to a number of characters, marking the cut with `…`.
+///
+/// This function backs the `s!(ellipsis = ..., max = ...; ...)` arm. `max`
+/// counts the characters kept from the original text, with the `…` marker added
+/// on top. Text that already fits is returned unchanged. Cuts always fall on
+/// `char` boundaries, and in `Mid` mode the extra character goes to the start.
+///
+/// # Arguments
+///
+/// * `text` - The text to shorten.
+/// * `max` - The number of characters kept from `text`.
+/// * `position` - Where the `…` marker is placed.
+///
+/// # Returns
+///
+/// The shortened `String`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::{ellipsize, Ellipsis};
+///
+/// assert_eq!(ellipsize("verylongfilename.txt", 10, Ellipsis::Mid), "veryl…e.txt");
+/// assert_eq!(ellipsize("short", 10, Ellipsis::End), "short");
+/// ```
+pub fn ellipsize(text: &str, max: usize, position: Ellipsis) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max {
+        return text.to_string();
+    }
+
+    let mut shortened = String::new();
+    match position {
+        Ellipsis::Start => {
+            shortened.push('…');
+            shortened.extend(&chars[chars.len() - max..]);
+        }
+        Ellipsis::Mid => {
+            let tail = max / 2;
+            shortened.extend(&chars[..max - tail]);
+            shortened.push('…');
+            shortened.extend(&chars[chars.len() - tail..]);
+        }
+        Ellipsis::End => {
+            shortened.extend(&chars[..max]);
+            shortened.push('…');
+        }
+    }
+    shortened
+}
+
+/// Pairs a count with the singular or plural form of a noun.
+///
+/// This function backs the `s!(plural ...)` arm. A count of exactly one uses
+/// the singular form. Any other count uses `plural` when given, otherwise the
+/// regular English plural: `-es` after `s`, `x`, `z`, `ch` or `sh`, `-ies` for a
+/// consonant followed by `y`, and `-s` for everything else.
+///
+/// # Arguments
+///
+/// * `count` - The number of items.
+/// * `singular` - The singular form of the noun.
+/// * `plural` - An irregular plural form, if the noun has one.
+///
+/// # Returns
+///
+/// A `String` such as `"1 item"` or `"3 items"`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::pluralize;
+///
+/// assert_eq!(pluralize(1, "box", None), "1 box");
+/// assert_eq!(pluralize(2, "box", None), "2 boxes");
+/// assert_eq!(pluralize(0, "city", None), "0 cities");
+/// assert_eq!(pluralize(2, "mouse", Some("mice")), "2 mice");
+/// ```
+pub fn pluralize<N>(count: N, singular: &str, plural: Option<&str>) -> String
+where
+    N: std::fmt::Display + PartialEq + From<u8>,
+{
+    if count == N::from(1) {
+        return format!("{} {}", count, singular);
+    }
+
+    let noun = match plural {
+        Some(plural) => plural.to_string(),
+        None => {
+            let lower = singular.to_lowercase();
+            let before_y = lower.chars().rev().nth(1);
+            if ["s", "x", "z", "ch", "sh"].iter().any(|end| lower.ends_with(end)) {
+                format!("{}es", singular)
+            } else if lower.ends_with('y') && matches!(before_y, Some(c) if !"aeiou".contains(c)) {
+                format!("{}ies", &singular[..singular.len() - 1])
+            } else {
+                format!("{}s", singular)
+            }
+        }
+    };
+    format!("{} {}", count, noun)
+}
+
+/// Collects the keys of a HashMap whose values satisfy a predicate.
+///
+/// This function backs the `keys_where!` macro. The keys come back in the
+/// map's iteration order, which is unspecified.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to search.
+/// * `predicate` - A closure called with each value.
+///
+/// # Returns
+///
+/// A `Vec` of references to every key whose value matched.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::keys_where;
+///
+/// let ages: std::collections::HashMap<_, _> = vec![("ann", 31), ("bob", 17)].into_iter().collect();
+/// assert_eq!(keys_where(&ages, |age| *age >= 18), vec![&"ann"]);
+/// ```
+pub fn keys_where<K, V, F>(map: &std::collections::HashMap<K, V>, mut predicate: F) -> Vec<&K>
+where
+    F: FnMut(&V) -> bool,
+{
+    map.iter().filter(|(_, value)| predicate(value)).map(|(key, _)| key).collect()
+}
+
+/// Masks the middle of a sensitive value, keeping only its first and last characters.
+///
+/// This function backs the `s!(mask; ...)` arm. Every hidden character is
+/// replaced with `*`. Values of two characters or fewer are masked entirely,
+/// since showing both ends would reveal the whole value.
+///
+/// # Arguments
+///
+/// * `text` - The value to mask.
+///
+/// # Returns
+///
+/// A `String` with the same number of characters as `text`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::mask;
+///
+/// assert_eq!(mask("secret"), "s****t");
+/// assert_eq!(mask("ab"), "**");
+/// ```
+pub fn mask(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= 2 {
+        return "*".repeat(count);
+    }
+
+    let mut chars = text.chars();
+    let (first, last) = (chars.next().unwrap_or_default(), chars.next_back().unwrap_or_default());
+    format!("{}{}{}", first, "*".repeat(count - 2), last)
+}
+
+/// Transposes a rectangular `Vec` of rows into a `Vec` of columns.
+///
+/// This function backs the `transpose!` macro. Elements are moved, not
+/// cloned, so any element type works.
+///
+/// # Arguments
+///
+/// * `rows` - The rows to transpose, all of the same length.
+///
+/// # Returns
+///
+/// A `Vec` where row `i` holds the `i`-th element of every input row.
+///
+/// # Panics
+///
+/// Panics if the rows do not all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::transpose;
+///
+/// assert_eq!(transpose(vec![vec![1, 2], vec![3, 4]]), vec![vec![1, 3], vec![2, 4]]);
+/// ```
+pub fn transpose<T>(rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let width = rows.first().map_or(0, Vec::len);
+    for (index, row) in rows.iter().enumerate() {
+        if row.len() != width {
+            panic!("transpose: row {} has {} elements, expected {} like the first row", index, row.len(), width);
+        }
+    }
+
+    let mut columns: Vec<Vec<T>> = (0..width).map(|_| Vec::with_capacity(rows.len())).collect();
+    for row in rows {
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+    columns
+}
+
+/// Collects the entries of a HashMap sorted by key.
+///
+/// This function gives the map macros a stable order to work in, regardless of
+/// the HashMap's own iteration order.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap whose entries are collected.
+///
+/// # Returns
+///
+/// A `Vec` of key-value references in ascending key order.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::sorted_entries;
+///
+/// let map: std::collections::HashMap<_, _> = vec![("b", 2), ("a", 1)].into_iter().collect();
+/// assert_eq!(sorted_entries(&map), vec![(&"a", &1), (&"b", &2)]);
+/// ```
+pub fn sorted_entries<K, V>(map: &std::collections::HashMap<K, V>) -> Vec<(&K, &V)>
+where
+    K: Ord,
+{
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Maps half-open ranges to values, looking values up by the range they fall in.
+///
+/// This is the structure built by the `range_map!` macro. Ranges are checked
+/// in insertion order, so when ranges overlap the first one inserted wins.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::RangeMap;
+///
+/// let mut grades = RangeMap::new();
+/// grades.insert(90..101, 'A');
+/// grades.insert(80..90, 'B');
+/// assert_eq!(grades.get(&95), Some(&'A'));
+/// assert_eq!(grades.get(&42), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeMap<T, V> {
+    ranges: Vec<(std::ops::Range<T>, V)>,
+}
+
+impl<T, V> RangeMap<T, V> {
+    /// Creates an empty `RangeMap`.
+    pub fn new() -> Self {
+        RangeMap { ranges: Vec::new() }
+    }
+
+    /// Adds a range and the value returned for anything inside it.
+    pub fn insert(&mut self, range: std::ops::Range<T>, value: V) {
+        self.ranges.push((range, value));
+    }
+
+    /// Returns the value of the first range containing `value`, if any.
+    pub fn get(&self, value: &T) -> Option<&V>
+    where
+        T: PartialOrd,
+    {
+        self.ranges.iter().find(|(range, _)| range.contains(value)).map(|(_, v)| v)
+    }
+
+    /// Returns the number of ranges in the map.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the map holds no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl<T, V> Default for RangeMap<T, V> {
+    fn default() -> Self {
+        RangeMap::new()
+    }
+}
+
+/// Identifies one cache of [`memoize`]: the call site, the closure type and the key/value types.
+type MemoId = (usize, &'static str, std::any::TypeId, std::any::TypeId);
+
+/// The per-thread caches used by [`memoize`].
+type MemoCaches = std::collections::HashMap<MemoId, Box<dyn std::any::Any>>;
+
+thread_local! {
+    static MEMO_CACHES: std::cell::RefCell<MemoCaches> = std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+/// Returns the cached result for a key, computing and caching it on the first call.
+///
+/// This function backs the `memoize!` macro. Results are stored per `site`,
+/// per closure type and per thread, so each thread computes its own values.
+/// Because the closure type of a generic function includes its type
+/// parameters, every instantiation of that function gets its own cache.
+/// Cached values live for the rest of the thread and are never evicted. The
+/// computation runs outside the cache borrow, so it may itself call `memoize`.
+///
+/// # Arguments
+///
+/// * `site` - A static unique to the call site, whose address identifies the cache.
+/// * `key` - The key the result is cached under.
+/// * `compute` - Produces the value when nothing is cached for `key` yet.
+///
+/// # Returns
+///
+/// A clone of the cached or freshly computed value.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::memoize;
+///
+/// static SITE: u8 = 0;
+/// fn nine() -> i32 {
+///     memoize(&SITE, 3, || 9)
+/// }
+/// fn zero() -> i32 {
+///     memoize(&SITE, 3, || 0)
+/// }
+///
+/// assert_eq!(nine(), 9);
+/// assert_eq!(nine(), 9); // Already cached, so the closure is skipped
+/// assert_eq!(zero(), 0); // A different closure at the same site has its own cache
+/// ```
+pub fn memoize<K, V, F>(site: &'static u8, key: K, compute: F) -> V
+where
+    K: std::hash::Hash + Eq + 'static,
+    V: Clone + 'static,
+    F: FnOnce() -> V,
+{
+    let id = (
+        site as *const u8 as usize,
+        std::any::type_name::<F>(),
+        std::any::TypeId::of::<K>(),
+        std::any::TypeId::of::<V>(),
+    );
+    let cached = MEMO_CACHES.with(|caches| {
+        caches
+            .borrow()
+            .get(&id)
+            .and_then(|cache| cache.downcast_ref::<std::collections::HashMap<K, V>>())
+            .and_then(|cache| cache.get(&key).cloned())
+    });
+    if let Some(value) = cached {
+        return value;
+    }
+
+    let value = compute();
+    MEMO_CACHES.with(|caches| {
+        caches
+            .borrow_mut()
+            .entry(id)
+            .or_insert_with(|| Box::new(std::collections::HashMap::<K, V>::new()))
+            .downcast_mut::<std::collections::HashMap<K, V>>()
+            .expect("memoize: cache type is keyed by its own TypeIds")
+            .insert(key, value.clone());
+    });
+    value
+}
+
+/// Wraps text in ANSI escape codes.
+///
+/// This function backs the `s!(color ...)` arm, which maps each color or style
+/// name to its SGR code at compile time. All the codes are combined into a
+/// single escape sequence and the text is followed by a reset.
+///
+/// # Arguments
+///
+/// * `codes` - The numeric SGR codes, such as `"1"` for bold or `"31"` for red.
+/// * `text` - The text to color.
+///
+/// # Returns
+///
+/// The text wrapped in the escape sequence and a trailing reset.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::colorize;
+///
+/// assert_eq!(colorize(&["32"], "ok"), "\x1b[32mok\x1b[0m");
+/// assert_eq!(colorize(&["1", "31"], "no"), "\x1b[1;31mno\x1b[0m");
+/// ```
+pub fn colorize(codes: &[&str], text: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+}
+
+/// Converts an identifier to `snake_case`.
+///
+/// This function backs the `s!(snake; ...)` arm. A word boundary is placed
+/// before an uppercase letter that follows a lowercase letter or digit, and
+/// before the last capital of an acronym that starts a new word, so
+/// `"HTTPServer"` becomes `"http_server"`. Dashes and spaces become
+/// underscores and leading underscores are kept as they are.
+///
+/// # Arguments
+///
+/// * `text` - The identifier to convert.
+///
+/// # Returns
+///
+/// The identifier in `snake_case`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::to_snake_case;
+///
+/// assert_eq!(to_snake_case("myVariableName"), "my_variable_name");
+/// assert_eq!(to_snake_case("parseHTTPResponse"), "parse_http_response");
+/// assert_eq!(to_snake_case("_privateField"), "_private_field");
+/// ```
+pub fn to_snake_case(text: &str) -> String {
+    let (prefix, rest) = text.split_at(text.len() - text.trim_start_matches('_').len());
+    let chars: Vec<char> = rest.chars().collect();
+
+    let mut snake = String::from(prefix);
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if index > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+        if !c.is_uppercase() {
+            snake.push(c);
+            continue;
+        }
+
+        let previous = if index > 0 { Some(chars[index - 1]) } else { None };
+        let next = chars.get(index + 1);
+        let boundary = match previous {
+            Some(p) if p.is_lowercase() || p.is_numeric() => true,
+            Some(p) if p.is_uppercase() => matches!(next, Some(n) if n.is_lowercase()),
+            _ => false,
+        };
+        if boundary && !snake.ends_with('_') {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Converts an identifier to `camelCase`.
+///
+/// This function backs the `s!(camel; ...)` arm. The text is split into words
+/// on underscores, dashes and spaces, and every word after the first is
+/// capitalized. Words written entirely in uppercase are lowercased first, so
+/// `"HTTP_SERVER"` becomes `"httpServer"`, while mixed-case words are kept.
+/// Leading underscores are kept as they are.
+///
+/// # Arguments
+///
+/// * `text` - The identifier to convert.
+///
+/// # Returns
+///
+/// The identifier in `camelCase`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::to_camel_case;
+///
+/// assert_eq!(to_camel_case("my_variable_name"), "myVariableName");
+/// assert_eq!(to_camel_case("HTTP_SERVER"), "httpServer");
+/// assert_eq!(to_camel_case("_private_field"), "_privateField");
+/// ```
+pub fn to_camel_case(text: &str) -> String {
+    let (prefix, rest) = text.split_at(text.len() - text.trim_start_matches('_').len());
+    let words = rest
+        .split(|c: char| c == '_' || c == '-' || c.is_whitespace())
+        .filter(|word| !word.is_empty());
+
+    let mut camel = String::from(prefix);
+    for (index, word) in words.enumerate() {
+        let word = if word.chars().any(char::is_lowercase) { word.to_string() } else { word.to_lowercase() };
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if index == 0 {
+                camel.extend(first.to_lowercase());
+            } else {
+                camel.extend(first.to_uppercase());
+            }
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+/// Anything with a length, used by the `assert_len!` macro.
+///
+/// Implemented for the standard collections, strings, slices and arrays, as
+/// well as references to any of them.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::Len;
+///
+/// assert_eq!(Len::len(&vec![1, 2, 3]), 3);
+/// assert_eq!(Len::len("four"), 4);
+/// assert!(Len::is_empty(&String::new()));
+/// ```
+pub trait Len {
+    /// Returns the number of elements, or bytes for strings.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the length is zero.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! impl_len {
+    ($($ty:ty => [$($generics:tt)*]),+ $(,)?) => {
+        $(
+            impl<$($generics)*> Len for $ty {
+                fn len(&self) -> usize {
+                    <$ty>::len(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_len!(
+    str => [],
+    String => [],
+    [T] => [T],
+    Vec<T> => [T],
+    std::collections::VecDeque<T> => [T],
+    std::collections::LinkedList<T> => [T],
+    std::collections::BinaryHeap<T> => [T],
+    std::collections::HashMap<K, V, S> => [K, V, S],
+    std::collections::HashSet<T, S> => [T, S],
+    std::collections::BTreeMap<K, V> => [K, V],
+    std::collections::BTreeSet<T> => [T],
+);
+
+impl<T, const N: usize> Len for [T; N] {
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+impl<T: Len + ?Sized> Len for &T {
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+}
+
+/// Builds a HashMap from items, deriving each key and value with a closure.
+///
+/// This function backs the `map!(from ...)` arm. Both closures receive a
+/// reference to the item, so the items themselves are never split apart.
+///
+/// # Arguments
+///
+/// * `items` - An iterator over the items.
+/// * `key` - A closure deriving the key from an item.
+/// * `value` - A closure deriving the value from an item.
+///
+/// # Returns
+///
+/// A HashMap of every derived key-value pair. Later items win on duplicate keys.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::map_by;
+///
+/// let lengths = map_by(vec!["one", "three"], |word| *word, |word| word.len());
+/// assert_eq!(lengths.get("three"), Some(&5));
+/// ```
+pub fn map_by<T, K, V, I, KF, VF>(items: I, mut key: KF, mut value: VF) -> std::collections::HashMap<K, V>
+where
+    K: std::hash::Hash + Eq,
+    I: std::iter::IntoIterator<Item = T>,
+    KF: FnMut(&T) -> K,
+    VF: FnMut(&T) -> V,
+{
+    items.into_iter().map(|item| (key(&item), value(&item))).collect()
+}
+
+/// Computes the CRC-32 (IEEE 802.3) checksum of some bytes.
+///
+/// This function backs the `s!(crc32; ...)` arm. It uses the reflected
+/// polynomial `0xEDB88320`, the same variant as zlib, PNG and Ethernet.
+///
+/// # Arguments
+///
+/// * `bytes` - The data to checksum, such as a `&str`, `Vec<u8>` or byte array.
+///
+/// # Returns
+///
+/// The checksum as a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::crc32;
+///
+/// assert_eq!(crc32("123456789"), 0xCBF4_3926);
+/// assert_eq!(crc32(b""), 0);
+/// ```
+pub fn crc32<B: AsRef<[u8]>>(bytes: B) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes.as_ref() {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Builds a HashMap from keys, computing each value from its key.
+///
+/// This function backs the `map!(keys: ..., default_fn: ...)` arm.
+///
+/// # Arguments
+///
+/// * `keys` - An iterator over the keys.
+/// * `value` - A closure computing the value for a key.
+///
+/// # Returns
+///
+/// A HashMap of every key paired with its computed value.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::map_from_keys;
+///
+/// let squares = map_from_keys(1..=3, |n| n * n);
+/// assert_eq!(squares.get(&3), Some(&9));
+/// ```
+pub fn map_from_keys<K, V, I, F>(keys: I, mut value: F) -> std::collections::HashMap<K, V>
+where
+    K: std::hash::Hash + Eq,
+    I: std::iter::IntoIterator<Item = K>,
+    F: FnMut(&K) -> V,
+{
+    keys.into_iter()
+        .map(|key| {
+            let value = value(&key);
+            (key, value)
+        })
+        .collect()
+}
+
+/// Draws a Unicode box around text.
+///
+/// This function backs the `s!(frame; ...)` arm. The box is sized to the
+/// longest line, with one space of padding on each side. Widths are counted
+/// in `char`s, so wide characters such as CJK or emoji can misalign the border.
+///
+/// # Arguments
+///
+/// * `text` - The text to frame, which may span multiple lines.
+///
+/// # Returns
+///
+/// The framed text, with lines separated by `\n` and no trailing newline.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::frame;
+///
+/// assert_eq!(frame("Hi"), "┌────┐\n│ Hi │\n└────┘");
+/// ```
+pub fn frame(text: &str) -> String {
+    let lines: Vec<&str> = if text.is_empty() { vec![""] } else { text.lines().collect() };
+    let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let border = "─".repeat(width + 2);
+
+    let mut framed = format!("┌{}┐\n", border);
+    for line in lines {
+        let padding = " ".repeat(width - line.chars().count());
+        framed.push_str(&format!("│ {}{} │\n", line, padding));
+    }
+    framed.push_str(&format!("└{}┘", border));
+    framed
+}
+
+/// Splits a HashMap into a number of smaller HashMaps.
+///
+/// This function backs the `map_chunks!` macro. Entries are sorted by key and
+/// handed out in contiguous runs, so the split is always the same for the same
+/// map. Chunk sizes differ by at most one, with the earlier chunks taking the
+/// extra entries, and chunks are left empty when there are fewer entries than
+/// chunks.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to split.
+/// * `chunks` - How many HashMaps to split it into.
+///
+/// # Returns
+///
+/// A `Vec` of exactly `chunks` HashMaps.
+///
+/// # Panics
+///
+/// Panics if `chunks` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::map_chunks;
+///
+/// let map: std::collections::HashMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+/// let chunks = map_chunks(map, 2);
+/// assert_eq!(chunks[0].len(), 2);
+/// assert_eq!(chunks[1].get(&3), Some(&'c'));
+/// ```
+pub fn map_chunks<K, V>(map: std::collections::HashMap<K, V>, chunks: usize) -> Vec<std::collections::HashMap<K, V>>
+where
+    K: std::hash::Hash + Eq + Ord,
+{
+    assert!(chunks > 0, "map_chunks: the number of chunks must be greater than zero");
+    let mut entries: Vec<(K, V)> = map.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let (size, extra) = (entries.len() / chunks, entries.len() % chunks);
+    let mut entries = entries.into_iter();
+    (0..chunks)
+        .map(|index| entries.by_ref().take(size + usize::from(index < extra)).collect())
+        .collect()
+}
+
+/// Clamps every value of a HashMap into the range `[min, max]`.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap whose values are clamped.
+/// * `min` - The lowest value allowed.
+/// * `max` - The highest value allowed.
+///
+/// # Returns
+///
+/// A HashMap with the same keys, and values raised to `min` or lowered to `max` where needed.
+///
+/// # Panics
+///
+/// Panics if `min` is greater than `max`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{clamp_values, map};
+///
+/// let clamped = clamp_values(map!("low" => -5, "mid" => 5, "high" => 50), 0, 10);
+/// assert_eq!(clamped["low"], 0);   // Below the bounds
+/// assert_eq!(clamped["mid"], 5);   // Within the bounds
+/// assert_eq!(clamped["high"], 10); // Above the bounds
+///
+/// let edges = clamp_values(map!("min" => 0, "max" => 10), 0, 10);
+/// assert_eq!(edges, map!("min" => 0, "max" => 10)); // The bounds themselves are kept
+/// ```
+pub fn clamp_values<K, V>(map: std::collections::HashMap<K, V>, min: V, max: V) -> std::collections::HashMap<K, V>
+where
+    K: std::hash::Hash + Eq,
+    V: Ord + Clone,
+{
+    assert!(min <= max, "clamp_values: `min` must not be greater than `max`");
+    map.into_iter()
+        .map(|(key, value)| (key, value.clamp(min.clone(), max.clone())))
+        .collect()
+}
+
+/// An integer accepted by `number_to_words`.
+///
+/// Implemented for every primitive integer type. The value is split into its
+/// sign and its magnitude, so the full range of every type, `u128` included,
+/// can be spelled out.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::WordsNumber;
+///
+/// assert_eq!(WordsNumber::sign_magnitude(&-42i8), (true, 42));
+/// assert_eq!(WordsNumber::sign_magnitude(&u128::MAX), (false, u128::MAX));
+/// ```
+pub trait WordsNumber {
+    /// Returns whether the number is below zero, and its absolute value.
+    fn sign_magnitude(&self) -> (bool, u128);
+}
+
+macro_rules! impl_words_number {
+    (signed: $($signed:ty),+; unsigned: $($unsigned:ty),+) => {
+        $(
+            impl WordsNumber for $signed {
+                fn sign_magnitude(&self) -> (bool, u128) {
+                    (*self < 0, self.unsigned_abs() as u128)
+                }
+            }
+        )+
+        $(
+            impl WordsNumber for $unsigned {
+                fn sign_magnitude(&self) -> (bool, u128) {
+                    (false, *self as u128)
+                }
+            }
+        )+
+    };
+}
+
+impl_words_number!(
+    signed: i8, i16, i32, i64, i128, isize;
+    unsigned: u8, u16, u32, u64, u128, usize
+);
+
+/// Spells out an integer in English words.
+///
+/// This function backs the `s!(words; ...)` arm. Tens and ones are joined
+/// with a hyphen (`"forty-two"`), larger groups are separated by spaces and no
+/// `"and"` is inserted. Negative numbers are prefixed with `"negative "`. Any
+/// primitive integer type is accepted over its full range, including `usize`
+/// and `u128`.
+///
+/// # Arguments
+///
+/// * `number` - The integer to spell out.
+///
+/// # Returns
+///
+/// The number in words, such as `"one thousand two hundred thirty-four"`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::number_to_words;
+///
+/// assert_eq!(number_to_words(-15), "negative fifteen");
+/// assert_eq!(number_to_words(2_000_001), "two million one");
+/// assert_eq!(number_to_words(vec![1, 2, 3].len()), "three");
+/// assert_eq!(number_to_words(u64::MAX).split(' ').next(), Some("eighteen"));
+/// assert_eq!(number_to_words(u128::MAX).split(' ').take(3).collect::<Vec<_>>(), ["three", "hundred", "forty"]);
+/// ```
+pub fn number_to_words<N: WordsNumber>(number: N) -> String {
+    let (negative, mut magnitude) = number.sign_magnitude();
+    const ONES: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+    const SCALES: [&str; 13] = [
+        "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion", "sextillion", "septillion",
+        "octillion", "nonillion", "decillion", "undecillion",
+    ];
+
+    // Spells out 1 through 999
+    fn hundreds(n: u128) -> String {
+        let mut words = Vec::new();
+        if n >= 100 {
+            words.push(format!("{} hundred", ONES[(n / 100) as usize]));
+        }
+        match n % 100 {
+            0 => {}
+            rest @ 1..=19 => words.push(ONES[rest as usize].to_string()),
+            rest if rest % 10 == 0 => words.push(TENS[(rest / 10) as usize].to_string()),
+            rest => words.push(format!("{}-{}", TENS[(rest / 10) as usize], ONES[(rest % 10) as usize])),
+        }
+        words.join(" ")
+    }
+
+    if magnitude == 0 {
+        return ONES[0].to_string();
+    }
+
+    let mut groups = Vec::new();
+    let mut scale = 0;
+    while magnitude > 0 {
+        let group = magnitude % 1000;
+        if group > 0 {
+            let words = hundreds(group);
+            groups.push(if scale > 0 { format!("{} {}", words, SCALES[scale]) } else { words });
+        }
+        magnitude /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+
+    let words = groups.join(" ");
+    if negative { format!("negative {}", words) } else { words }
+}
+
+/// Escapes text for use inside a JSON string literal.
+///
+/// Quotes, backslashes and control characters are escaped. The surrounding
+/// quotes are not added.
+///
+/// # Arguments
+///
+/// * `text` - The text to escape.
+///
+/// # Returns
+///
+/// The escaped text.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::json_escape;
+///
+/// assert_eq!(json_escape("say \"hi\"\n"), "say \\\"hi\\\"\\n");
+/// ```
+pub fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A value that can be rendered as JSON, used by the `map_to_json!` macro.
+///
+/// Strings are quoted and escaped, numbers and booleans are written bare,
+/// and `None` becomes `null`. Non-finite floats have no JSON form and are
+/// also written as `null`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::JsonValue;
+///
+/// assert_eq!("hi".to_json(), "\"hi\"");
+/// assert_eq!(3.5.to_json(), "3.5");
+/// assert_eq!(None::<u8>.to_json(), "null");
+/// ```
+pub trait JsonValue {
+    /// Renders the value as JSON.
+    fn to_json(&self) -> String;
+}
+
+impl JsonValue for str {
+    fn to_json(&self) -> String {
+        format!("\"{}\"", json_escape(self))
+    }
+}
+
+impl JsonValue for String {
+    fn to_json(&self) -> String {
+        self.as_str().to_json()
+    }
+}
+
+impl JsonValue for char {
+    fn to_json(&self) -> String {
+        self.to_string().to_json()
+    }
+}
+
+impl JsonValue for bool {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+macro_rules! impl_json_number {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl JsonValue for $ty {
+                fn to_json(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )+
+    };
+}
+
+impl_json_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_json_float {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl JsonValue for $ty {
+                fn to_json(&self) -> String {
+                    if self.is_finite() { self.to_string() } else { String::from("null") }
+                }
+            }
+        )+
+    };
+}
+
+impl_json_float!(f32, f64);
+
+impl<T: JsonValue> JsonValue for Option<T> {
+    fn to_json(&self) -> String {
+        match self {
+            Some(value) => value.to_json(),
+            None => String::from("null"),
+        }
+    }
+}
+
+impl<T: JsonValue + ?Sized> JsonValue for &T {
+    fn to_json(&self) -> String {
+        T::to_json(self)
+    }
+}
+
+/// Serializes a HashMap as a JSON object.
+///
+/// This function backs the `map_to_json!` macro. Keys are converted with
+/// `to_string` and always quoted, values are rendered through [`JsonValue`].
+/// Entries are sorted by their key string so the output is deterministic.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to serialize.
+///
+/// # Returns
+///
+/// A compact JSON object, such as `{"a":1,"b":"two"}`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{helpers::map_to_json, map};
+///
+/// assert_eq!(map_to_json(&map!(2 => true, 1 => false)), "{\"1\":false,\"2\":true}");
+/// ```
+pub fn map_to_json<K, V>(map: &std::collections::HashMap<K, V>) -> String
+where
+    K: std::fmt::Display,
+    V: JsonValue,
+{
+    let mut entries: Vec<(String, String)> = map.iter().map(|(key, value)| (key.to_string(), value.to_json())).collect();
+    entries.sort();
+    let members: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| format!("{}:{}", key.as_str().to_json(), value))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// Calls a producer until its result satisfies a predicate, up to a maximum number of attempts.
+///
+/// This function backs the `retry_until!` macro. There is no delay between
+/// attempts.
+///
+/// # Arguments
+///
+/// * `max` - The maximum number of attempts.
+/// * `producer` - A closure producing a new result on each attempt.
+/// * `predicate` - A closure deciding whether a result is good enough.
+///
+/// # Returns
+///
+/// The first result that satisfied the predicate, or the last result if none did.
+///
+/// # Panics
+///
+/// Panics if `max` is zero, since there would be no result to return.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::retry_until;
+///
+/// let mut n = 0;
+/// assert_eq!(retry_until(10, || { n += 1; n }, |n| n % 4 == 0), 4);
+/// ```
+pub fn retry_until<T, P, F>(max: usize, mut producer: P, mut predicate: F) -> T
+where
+    P: FnMut() -> T,
+    F: FnMut(&T) -> bool,
+{
+    assert!(max > 0, "retry_until: `max` must be at least one attempt");
+    let mut result = producer();
+    for _ in 1..max {
+        if predicate(&result) {
+            break;
+        }
+        result = producer();
+    }
+    result
+}
+
+/// Groups the integer digits of a number by threes with a separator.
+///
+/// A leading `-` or `+` sign is kept in front, and anything from the first
+/// `.` onwards is left untouched, so decimals can be grouped as well.
+///
+/// # Arguments
+///
+/// * `number` - The number as text, such as `"1234567"` or `"-1234.5"`.
+/// * `separator` - The text placed between each group of three digits.
+///
+/// # Returns
+///
+/// The grouped number.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::group_digits;
+///
+/// assert_eq!(group_digits("1234567", ","), "1,234,567");
+/// assert_eq!(group_digits("-1234.5678", "_"), "-1_234.5678");
+/// assert_eq!(group_digits("999", ","), "999");
+/// ```
+pub fn group_digits(number: &str, separator: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-').or_else(|| number.strip_prefix('+')) {
+        Some(rest) => number.split_at(number.len() - rest.len()),
+        None => ("", number),
+    };
+    let (digits, rest) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+
+    let mut grouped = String::from(sign);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(rest);
+    grouped
+}
+
+/// An amount of money accepted by `format_money`.
+///
+/// Implemented for every primitive integer type and for `f32` and `f64`.
+/// Integers are whole units and are formatted exactly, while floats are
+/// rounded to two decimal places.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::MoneyAmount;
+///
+/// assert_eq!(MoneyAmount::money_parts(&-42i64), (true, String::from("42.00")));
+/// assert_eq!(MoneyAmount::money_parts(&2.5f64), (false, String::from("2.50")));
+/// ```
+pub trait MoneyAmount {
+    /// Returns whether the amount is below zero, and its magnitude with two decimals.
+    fn money_parts(&self) -> (bool, String);
+}
+
+macro_rules! impl_money_amount {
+    (signed: $($signed:ty),+; unsigned: $($unsigned:ty),+; float: $($float:ty),+) => {
+        $(
+            impl MoneyAmount for $signed {
+                fn money_parts(&self) -> (bool, String) {
+                    (*self < 0, format!("{}.00", self.unsigned_abs()))
+                }
+            }
+        )+
+        $(
+            impl MoneyAmount for $unsigned {
+                fn money_parts(&self) -> (bool, String) {
+                    (false, format!("{}.00", self))
+                }
+            }
+        )+
+        $(
+            impl MoneyAmount for $float {
+                fn money_parts(&self) -> (bool, String) {
+                    assert!(self.is_finite(), "format_money: the amount must be finite");
+                    let rounded = format!("{:.2}", self.abs());
+                    (*self < 0.0 && rounded.chars().any(|c| c != '0' && c != '.'), rounded)
+                }
+            }
+        )+
+    };
+}
+
+impl_money_amount!(
+    signed: i8, i16, i32, i64, i128, isize;
+    unsigned: u8, u16, u32, u64, u128, usize;
+    float: f32, f64
+);
+
+/// Formats an amount of money with a currency symbol, thousands separators and two decimals.
+///
+/// This function backs the `s!(money ...)` arm. Integer amounts of any width
+/// are whole units and are formatted exactly; float amounts are rounded to two
+/// decimal places. Negative amounts put the sign before the symbol.
+///
+/// # Arguments
+///
+/// * `amount` - The amount of money, an integer or a float.
+/// * `symbol` - The currency symbol placed before the amount.
+///
+/// # Returns
+///
+/// A `String` such as `"$1,234.50"`.
+///
+/// # Panics
+///
+/// Panics if `amount` is a float that is `NaN` or infinite.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::format_money;
+///
+/// assert_eq!(format_money(1234.5, "$"), "$1,234.50");
+/// assert_eq!(format_money(-0.5, "£"), "-£0.50");
+/// assert_eq!(format_money(1000000, "¥"), "¥1,000,000.00");
+/// assert_eq!(format_money(i64::MIN, "$"), "-$9,223,372,036,854,775,808.00");
+/// assert_eq!(format_money(vec![1, 2].len(), "$"), "$2.00");
+/// ```
+///
+/// ```should_panic
+/// use simplicio::helpers::format_money;
+///
+/// format_money(f64::NAN, "$"); // Panics: the amount must be finite
+/// ```
+pub fn format_money<N: MoneyAmount>(amount: N, symbol: &str) -> String {
+    let (negative, magnitude) = amount.money_parts();
+    let sign = if negative { "-" } else { "" };
+    format!("{}{}{}", sign, symbol, group_digits(&magnitude, ","))
+}
+
+/// Extends a HashMap with entries, resolving key conflicts with a closure.
+///
+/// This function backs the resolver form of the `map_merge_assign!` macro.
+/// Keys only in `source` are inserted as they are. For keys in both, the
+/// resolver receives the existing value and the incoming value, and its result
+/// is stored.
+///
+/// # Arguments
+///
+/// * `target` - The HashMap extended in place.
+/// * `source` - The entries merged into `target`.
+/// * `resolve` - A closure combining the old and new value of a conflicting key.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{helpers::merge_with, map};
+///
+/// let mut totals = map!("a" => 1);
+/// merge_with(&mut totals, vec![("a", 2), ("b", 3)], |old, new| old + new);
+/// assert_eq!(totals, map!("a" => 3, "b" => 3));
+/// ```
+pub fn merge_with<K, V, I, F>(target: &mut std::collections::HashMap<K, V>, source: I, mut resolve: F)
+where
+    K: std::hash::Hash + Eq,
+    I: std::iter::IntoIterator<Item = (K, V)>,
+    F: FnMut(V, V) -> V,
+{
+    for (key, value) in source {
+        let value = match target.remove(&key) {
+            Some(old) => resolve(old, value),
+            None => value,
+        };
+        target.insert(key, value);
+    }
+}
+
+// Panics with a message naming `table` if any row's width differs from the header's
+fn check_row_widths(table: &str, headers: &[String], rows: &[Vec<String>]) {
+    for (index, row) in rows.iter().enumerate() {
+        if row.len() != headers.len() {
+            panic!("{}: row {} has {} cells, expected {} to match the headers", table, index, row.len(), headers.len());
+        }
+    }
+}
+
+/// Builds a GitHub-flavored markdown table.
+///
+/// This function backs the `s!(md_table; ...)` arm. A `---` separator row
+/// follows the header, any `|` inside a cell is escaped and line breaks in a
+/// cell become `<br>`, so every row stays on one line.
+///
+/// # Arguments
+///
+/// * `headers` - The column headers.
+/// * `rows` - The table rows, each with one cell per header.
+///
+/// # Returns
+///
+/// The table, with rows separated by `\n` and no trailing newline.
+///
+/// # Panics
+///
+/// Panics if `headers` is empty, since markdown has no table without columns,
+/// or if a row does not have exactly one cell per header.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::markdown_table;
+///
+/// let table = markdown_table(&[String::from("Name")], &[vec![String::from("a|b")]]);
+/// assert_eq!(table, "| Name |\n| --- |\n| a\\|b |");
+///
+/// let table = markdown_table(&[String::from("Note")], &[vec![String::from("x\ny")]]);
+/// assert_eq!(table, "| Note |\n| --- |\n| x<br>y |");
+/// ```
+///
+/// ```should_panic
+/// use simplicio::helpers::markdown_table;
+///
+/// markdown_table(&[], &[]); // Panics: a table needs at least one header
+/// ```
+pub fn markdown_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    if headers.is_empty() {
+        panic!("markdown_table: a table needs at least one header");
+    }
+    check_row_widths("markdown_table", headers, rows);
+    let render = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace("\r\n", "<br>").replace('\n', "<br>"))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let mut lines = vec![render(headers), format!("|{}", " --- |".repeat(headers.len()))];
+    lines.extend(rows.iter().map(|row| render(row)));
+    lines.join("\n")
+}
+
+/// Lists the keys that are not present in a HashMap.
+///
+/// This function backs the `debug_assert_keys!` macro. Each key is a borrowed
+/// form of the map's key type, as taken by `HashMap::contains_key`, so a
+/// `HashMap<String, V>` can be checked with `&str` keys.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to check.
+/// * `keys` - The keys that should be present.
+///
+/// # Returns
+///
+/// The missing keys, in the order they were given.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{helpers::missing_keys, map};
+/// use std::collections::HashMap;
+///
+/// let map = map!("a" => 1, "b" => 2);
+/// assert_eq!(missing_keys(&map, &["a", "c", "d"]), vec!["c", "d"]);
+///
+/// let mut owned = HashMap::new();
+/// owned.insert(String::from("a"), 1);
+/// assert_eq!(missing_keys(&owned, &["a", "b"]), vec!["b"]);
+/// ```
+pub fn missing_keys<'k, K, V, Q>(map: &std::collections::HashMap<K, V>, keys: &[&'k Q]) -> Vec<&'k Q>
+where
+    K: std::borrow::Borrow<Q> + std::hash::Hash + Eq,
+    Q: std::hash::Hash + Eq + ?Sized,
+{
+    keys.iter().copied().filter(|key| !map.contains_key(*key)).collect()
+}
+
+/// Lays items out in columns of a fixed width, filling each row before the next.
+///
+/// This function backs the `s!(columns = ..., width = ...; ...)` arm. Every
+/// item is padded to `width` characters and cells are separated by a single
+/// space, so neighbouring items never run together. Items wider than `width`
+/// are kept whole and push the rest of their row to the right. Trailing
+/// spaces are trimmed from each line.
+///
+/// # Arguments
+///
+/// * `items` - The items to lay out.
+/// * `columns` - The number of items per row.
+/// * `width` - The width of each column, in characters.
+///
+/// # Returns
+///
+/// The rows, separated by `\n` with no trailing newline.
+///
+/// # Panics
+///
+/// Panics if `columns` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::columns;
+///
+/// assert_eq!(columns(vec!["a", "b", "c"], 2, 3), "a   b\nc");
+/// assert_eq!(columns(vec!["abc", "d", "toolong", "e"], 2, 3), "abc d\ntoolong e");
+/// ```
+pub fn columns<I>(items: I, columns: usize, width: usize) -> String
+where
+    I: std::iter::IntoIterator,
+    I::Item: std::fmt::Display,
+{
+    assert!(columns > 0, "columns: the number of columns must be greater than zero");
+    let cells: Vec<String> = items
+        .into_iter()
+        .map(|item| format!("{:<width$}", item.to_string(), width = width))
+        .collect();
+    let lines: Vec<String> = cells
+        .chunks(columns)
+        .map(|row| row.join(" ").trim_end().to_string())
+        .collect();
+    lines.join("\n")
+}
+
+/// Builds a bordered ASCII table with aligned, left-justified columns.
+///
+/// This function backs the `s!(map_table; ...)` arm. Each column is as wide
+/// as its widest cell, counted in `char`s, and the header is separated from
+/// the rows by a border line.
+///
+/// # Arguments
+///
+/// * `headers` - The column headers.
+/// * `rows` - The table rows, each with one cell per header.
+///
+/// # Returns
+///
+/// The table, with lines separated by `\n` and no trailing newline.
+///
+/// # Panics
+///
+/// Panics if a row does not have exactly one cell per header.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::ascii_table;
+///
+/// let table = ascii_table(&[String::from("Id")], &[vec![String::from("7")]]);
+/// assert_eq!(table, "+----+\n| Id |\n+----+\n| 7  |\n+----+");
+/// ```
+pub fn ascii_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    check_row_widths("ascii_table", headers, rows);
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            std::iter::once(&headers[column])
+                .chain(rows.iter().map(|row| &row[column]))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = widths.iter().map(|width| format!("+{}", "-".repeat(width + 2))).collect::<String>() + "+";
+    let render = |cells: &[String]| {
+        let cells: String = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("| {:<width$} ", cell, width = width))
+            .collect();
+        cells + "|"
+    };
+
+    let mut lines = vec![border.clone(), render(headers), border.clone()];
+    lines.extend(rows.iter().map(|row| render(row)));
+    lines.push(border);
+    lines.join("\n")
+}
+
+/// Summary statistics over the values of a HashMap, returned by [`value_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// The number of values.
+    pub count: usize,
+    /// The sum of the values, `0.0` when there are none.
+    pub sum: f64,
+    /// The smallest value, if there are any.
+    pub min: Option<f64>,
+    /// The largest value, if there are any.
+    pub max: Option<f64>,
+    /// The arithmetic mean of the values, if there are any.
+    pub mean: Option<f64>,
+}
+
+/// Computes the count, sum, min, max and mean of a HashMap's numeric values.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap whose values are summarized.
+///
+/// # Returns
+///
+/// The [`Stats`] of the values. An empty map has a count and sum of zero and
+/// no min, max or mean.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, value_stats, Stats};
+///
+/// let stats = value_stats(&map!("a" => 2, "b" => 7, "c" => 3));
+/// assert_eq!(stats.count, 3);
+/// assert_eq!(stats.sum, 12.0);
+/// assert_eq!(stats.min, Some(2.0));
+/// assert_eq!(stats.max, Some(7.0));
+/// assert_eq!(stats.mean, Some(4.0));
+///
+/// let empty = value_stats(&std::collections::HashMap::<&str, f32>::new());
+/// assert_eq!(empty, Stats { count: 0, sum: 0.0, min: None, max: None, mean: None });
+/// ```
+pub fn value_stats<K, V>(map: &std::collections::HashMap<K, V>) -> Stats
+where
+    V: Into<f64> + Copy,
+{
+    let mut stats = Stats { count: 0, sum: 0.0, min: None, max: None, mean: None };
+    for value in map.values() {
+        let value: f64 = (*value).into();
+        stats.count += 1;
+        stats.sum += value;
+        stats.min = Some(stats.min.map_or(value, |min| min.min(value)));
+        stats.max = Some(stats.max.map_or(value, |max| max.max(value)));
+    }
+    if stats.count > 0 {
+        stats.mean = Some(stats.sum / stats.count as f64);
+    }
+    stats
+}
+
+/// Renders a two-level tree with box-drawing connectors.
+///
+/// This function backs the `s!(tree; ...)` arm. Each child is listed under
+/// the root, with its own children, if any, indented beneath it.
+///
+/// # Arguments
+///
+/// * `root` - The label of the root.
+/// * `children` - Each child's label paired with the labels of its children.
+///
+/// # Returns
+///
+/// The tree, with lines separated by `\n` and no trailing newline.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::render_tree;
+///
+/// let tree = render_tree("src", &[(String::from("lib.rs"), vec![])]);
+/// assert_eq!(tree, "src\n└── lib.rs");
+/// ```
+pub fn render_tree(root: &str, children: &[(String, Vec<String>)]) -> String {
+    let mut lines = vec![root.to_string()];
+    for (index, (child, leaves)) in children.iter().enumerate() {
+        let last = index + 1 == children.len();
+        lines.push(format!("{}{}", if last { "└── " } else { "├── " }, child));
+        for (leaf_index, leaf) in leaves.iter().enumerate() {
+            let indent = if last { "    " } else { "│   " };
+            let connector = if leaf_index + 1 == leaves.len() { "└── " } else { "├── " };
+            lines.push(format!("{}{}{}", indent, connector, leaf));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Finds the keys present in both of two HashMaps.
+///
+/// # Arguments
+///
+/// * `a` - The first HashMap.
+/// * `b` - The second HashMap, whose value type may differ from the first's.
+///
+/// # Returns
+///
+/// A `HashSet` of the keys found in both maps.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{common_keys, map};
+///
+/// let a = map!("x" => 1, "y" => 2, "z" => 3);
+/// let b = map!("y" => "yes", "z" => "zed", "w" => "why");
+/// let mut shared: Vec<_> = common_keys(&a, &b).into_iter().collect();
+/// shared.sort();
+/// assert_eq!(shared, vec!["y", "z"]); // Overlapping keys
+///
+/// let c = map!("q" => 0);
+/// assert!(common_keys(&a, &c).is_empty()); // Disjoint keys
+/// ```
+pub fn common_keys<K, V, W>(a: &std::collections::HashMap<K, V>, b: &std::collections::HashMap<K, W>) -> std::collections::HashSet<K>
+where
+    K: std::hash::Hash + Eq + Clone,
+{
+    a.keys().filter(|key| b.contains_key(*key)).cloned().collect()
+}
+
+/// Describes a signed offset in seconds as a relative time phrase.
+///
+/// This function backs the `s!(rel_time; ...)` arm. Positive offsets are in
+/// the future (`"in 2 hours"`) and negative ones in the past (`"3 days ago"`).
+/// Offsets under ten seconds either way are `"just now"`. The offset is
+/// rounded down to the largest fitting unit, where a month counts as 30 days
+/// and a year as 365 days.
+///
+/// # Arguments
+///
+/// * `seconds` - The offset from now, in seconds.
+///
+/// # Returns
+///
+/// The relative time phrase.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::relative_time;
+///
+/// assert_eq!(relative_time(90), "in 1 minute");
+/// assert_eq!(relative_time(-400 * 86_400), "1 year ago");
+/// ```
+pub fn relative_time(seconds: i64) -> String {
+    const UNITS: [(u64, &str); 6] = [
+        (365 * 86_400, "year"),
+        (30 * 86_400, "month"),
+        (86_400, "day"),
+        (3_600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+
+    let magnitude = seconds.unsigned_abs();
+    if magnitude < 10 {
+        return String::from("just now");
+    }
+
+    let (size, unit) = UNITS.iter().find(|(size, _)| magnitude >= *size).copied().unwrap_or(UNITS[5]);
+    let amount = pluralize(magnitude / size, unit, None);
+    if seconds > 0 { format!("in {}", amount) } else { format!("{} ago", amount) }
+}
+
+/// Calls a closure with every key and value of a HashMap.
+///
+/// This function backs the `map_apply!` macro. Entries are visited in the
+/// map's iteration order, which is unspecified.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to iterate.
+/// * `f` - A closure called with a reference to each key and value.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{helpers::for_each_entry, map};
+///
+/// let mut total = 0;
+/// for_each_entry(&map!("a" => 1, "b" => 2), |_, value| total += value);
+/// assert_eq!(total, 3);
+/// ```
+pub fn for_each_entry<K, V, F>(map: &std::collections::HashMap<K, V>, mut f: F)
+where
+    F: FnMut(&K, &V),
+{
+    for (key, value) in map {
+        f(key, value);
+    }
+}
+
+/// Truncates text to a byte budget without splitting a character.
+///
+/// This function backs the `s!(bytes_max = ...; ...)` arm. When the budget
+/// ends partway through a multibyte character, that whole character is
+/// dropped, so the result may be a little shorter than `max` bytes.
+///
+/// # Arguments
+///
+/// * `text` - The text to truncate.
+/// * `max` - The maximum length of the result, in bytes.
+///
+/// # Returns
+///
+/// The longest prefix of `text` that fits in `max` bytes and ends on a `char` boundary.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::truncate_bytes;
+///
+/// assert_eq!(truncate_bytes("añb", 2), "a"); // `ñ` takes two bytes
+/// assert_eq!(truncate_bytes("añb", 3), "añ");
+/// ```
+pub fn truncate_bytes(text: &str, max: usize) -> &str {
+    let mut end = max.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Keeps the `n` entries of a HashMap with the largest values.
+///
+/// When several entries tie for the last places, the ones with the smallest
+/// keys are kept, so the result never depends on the HashMap's iteration
+/// order. This is why keys must implement `Ord`. If `n` is at least the size
+/// of the map, every entry is kept.
+///
+/// # Arguments
+///
+/// * `map` - The HashMap to trim.
+/// * `n` - The number of entries to keep.
+///
+/// # Returns
+///
+/// A HashMap of the `n` entries with the largest values.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, top_n_by_value};
+///
+/// let scores = map!("ann" => 90, "bob" => 75, "cid" => 82, "dee" => 60);
+/// assert_eq!(top_n_by_value(scores.clone(), 2), map!("ann" => 90, "cid" => 82)); // Fewer than the map holds
+/// assert_eq!(top_n_by_value(scores.clone(), 4), scores); // As many as the map holds
+///
+/// let tied = map!("d" => 5, "b" => 5, "c" => 5, "a" => 1);
+/// assert_eq!(top_n_by_value(tied, 2), map!("b" => 5, "c" => 5)); // Ties keep the smallest keys
+/// ```
+pub fn top_n_by_value<K, V>(map: std::collections::HashMap<K, V>, n: usize) -> std::collections::HashMap<K, V>
+where
+    K: std::hash::Hash + Eq + Ord,
+    V: Ord,
+{
+    let mut entries: Vec<(K, V)> = map.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(n);
+    entries.into_iter().collect()
+}
+
+/// Draws a sparkline of values using the eight Unicode block levels `▁▂▃▄▅▆▇█`.
+///
+/// This function backs the `s!(sparkline; ...)` arm. Values are scaled
+/// between the smallest and the largest of them. When they are all equal the
+/// sparkline is drawn at a middle level.
+///
+/// # Arguments
+///
+/// * `values` - The values to plot.
+///
+/// # Returns
+///
+/// One block character per value.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::sparkline;
+///
+/// assert_eq!(sparkline(&[0.0, 7.0]), "▁█");
+/// assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+/// ```
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            if range > 0.0 {
+                BARS[((value - min) / range * 7.0).round() as usize]
+            } else {
+                BARS[3]
+            }
+        })
+        .collect()
+}
+
+/// Handles both sides of a `Result` with a closure each, returning a common type.
+///
+/// This function backs the `either!` macro.
+///
+/// # Arguments
+///
+/// * `result` - The `Result` to handle.
+/// * `on_ok` - A closure called with the `Ok` value.
+/// * `on_err` - A closure called with the `Err` value.
+///
+/// # Returns
+///
+/// Whatever the closure for the matching side returned.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::either;
+///
+/// let parsed: Result<u8, String> = Ok(7);
+/// assert_eq!(either(parsed, |n| n * 2, |_| 0), 14);
+/// ```
+pub fn either<T, E, R, O, F>(result: Result<T, E>, on_ok: O, on_err: F) -> R
+where
+    O: FnOnce(T) -> R,
+    F: FnOnce(E) -> R,
+{
+    match result {
+        Ok(value) => on_ok(value),
+        Err(err) => on_err(err),
+    }
+}
+
+/// Lines numbers up on their decimal points, right-aligned within a column.
+///
+/// This function backs the `s!(decimal_align = ...; ...)` arm. Integer parts
+/// are right-aligned and fractional parts left-aligned, so every decimal point
+/// lands in the same column, then each line is right-aligned to `width`. Lines
+/// are never cut when the numbers need more than `width` characters.
+///
+/// # Arguments
+///
+/// * `values` - The numbers to align.
+/// * `width` - The width of the column, in characters.
+///
+/// # Returns
+///
+/// One line per number, separated by `\n` with no trailing newline.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::decimal_align;
+///
+/// assert_eq!(decimal_align(vec![10.5, 2.25], 6), " 10.5 \n  2.25");
+/// ```
+pub fn decimal_align<I>(values: I, width: usize) -> String
+where
+    I: std::iter::IntoIterator,
+    I::Item: std::fmt::Display,
+{
+    let numbers: Vec<String> = values.into_iter().map(|value| value.to_string()).collect();
+    let parts: Vec<(&str, &str)> = numbers
+        .iter()
+        .map(|number| number.split_at(number.find('.').unwrap_or(number.len())))
+        .collect();
+    let integer_width = parts.iter().map(|(integer, _)| integer.chars().count()).max().unwrap_or(0);
+    let fraction_width = parts.iter().map(|(_, fraction)| fraction.chars().count()).max().unwrap_or(0);
+
+    let lines: Vec<String> = parts
+        .iter()
+        .map(|(integer, fraction)| {
+            let aligned = format!(
+                "{:>integer_width$}{:<fraction_width$}",
+                integer,
+                fraction,
+                integer_width = integer_width,
+                fraction_width = fraction_width
+            );
+            format!("{:>width$}", aligned, width = width)
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Pairs up the values of two HashMaps by key.
+///
+/// This function backs the `map_zip!` macro. Only keys present in both maps
+/// are included, with the values cloned out of each.
+///
+/// # Arguments
+///
+/// * `a` - The HashMap providing the first value of each pair.
+/// * `b` - The HashMap providing the second value of each pair.
+///
+/// # Returns
+///
+/// A HashMap from each shared key to its `(a, b)` values.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, zip_values};
+///
+/// let names = map!(1 => "ann", 2 => "bob");
+/// let ages = map!(1 => 29, 2 => 35);
+/// assert_eq!(zip_values(&names, &ages), map!(1 => ("ann", 29), 2 => ("bob", 35))); // Same keys
+///
+/// let scores = map!(2 => 7.5, 3 => 9.0);
+/// assert_eq!(zip_values(&names, &scores), map!(2 => ("bob", 7.5))); // Partially disjoint keys
+/// ```
+pub fn zip_values<K, V1, V2>(
+    a: &std::collections::HashMap<K, V1>,
+    b: &std::collections::HashMap<K, V2>,
+) -> std::collections::HashMap<K, (V1, V2)>
+where
+    K: std::hash::Hash + Eq + Clone,
+    V1: Clone,
+    V2: Clone,
+{
+    a.iter()
+        .filter_map(|(key, first)| b.get(key).map(|second| (key.clone(), (first.clone(), second.clone()))))
+        .collect()
+}
+
+/// Escapes text for use in HTML content or attribute values.
+///
+/// Replaces `&`, `<`, `>`, `"` and `'` with their character references.
+///
+/// # Arguments
+///
+/// * `text` - The text to escape.
+///
+/// # Returns
+///
+/// The escaped text.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::html_escape;
+///
+/// assert_eq!(html_escape("<a href=\"x\">Tom & Jerry's</a>"), "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;");
+/// ```
+pub fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits text into groups of characters joined by a separator.
+///
+/// This function backs the `chunked_string!` macro. Groups are counted in
+/// `char`s rather than bytes, and the last group holds whatever is left over.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+/// * `size` - The number of characters in each group.
+/// * `separator` - The text placed between groups.
+///
+/// # Returns
+///
+/// The grouped text.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::helpers::chunk_string;
+///
+/// assert_eq!(chunk_string("deadbeef", 2, ":"), "de:ad:be:ef");
+/// ```
+pub fn chunk_string(text: &str, size: usize, separator: &str) -> String {
+    assert!(size > 0, "chunk_string: the group size must be greater than zero");
+    let chars: Vec<char> = text.chars().collect();
+    let chunks: Vec<String> = chars.chunks(size).map(|chunk| chunk.iter().collect()).collect();
+    chunks.join(separator)
+}
+
+/// The differences between two HashMaps, returned by [`map_diff`].
+///
+/// Every list is sorted by key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapDiff<'a, K, V> {
+    /// Keys only in the new map.
+    pub added: Vec<&'a K>,
+    /// Keys only in the old map.
+    pub removed: Vec<&'a K>,
+    /// Keys in both maps whose value changed, with the old and the new value.
+    pub changed: Vec<(&'a K, &'a V, &'a V)>,
+}
+
+/// Compares two HashMaps, listing the added, removed and changed keys.
+///
+/// # Arguments
+///
+/// * `old` - The HashMap before the change.
+/// * `new` - The HashMap after the change.
+///
+/// # Returns
+///
+/// The [`MapDiff`] between the two maps. Unchanged keys are not listed.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_diff};
+///
+/// let old = map!("a" => 1, "b" => 2, "c" => 3);
+/// let new = map!("b" => 2, "c" => 4, "d" => 5);
+/// let diff = map_diff(&old, &new);
+/// assert_eq!(diff.added, vec![&"d"]);
+/// assert_eq!(diff.removed, vec![&"a"]);
+/// assert_eq!(diff.changed, vec![(&"c", &3, &4)]);
+/// ```
+pub fn map_diff<'a, K, V>(old: &'a std::collections::HashMap<K, V>, new: &'a std::collections::HashMap<K, V>) -> MapDiff<'a, K, V>
+where
+    K: std::hash::Hash + Eq + Ord,
+    V: PartialEq,
+{
+    let mut diff = MapDiff { added: Vec::new(), removed: Vec::new(), changed: Vec::new() };
+    for (key, value) in sorted_entries(new) {
+        match old.get(key) {
+            None => diff.added.push(key),
+            Some(previous) if previous != value => diff.changed.push((key, previous, value)),
+            Some(_) => {}
+        }
+    }
+    diff.removed = sorted_entries(old)
+        .into_iter()
+        .map(|(key, _)| key)
+        .filter(|key| !new.contains_key(*key))
+        .collect();
+    diff
 }
@@ -8,9 +8,74 @@
 //!   - convert any value that implements the `Display` trait into a string
 //!   - concatinate values together that implement the `Display` trait
 //!   - concatinate while inserting a space between each value with the `.` prefix (e.g. `s!(.a, b, c)`)
+//!   - fill `{key}` placeholders from named arguments (e.g. `s!(tpl "Hello {name}"; name = "World")`)
+//!   - reverse text by Unicode scalar value with `s!(reverse_str; "abc")`
+//!   - render a `Duration` as an ISO-8601 string (e.g. `"PT1H2M3S"`) with `s!(iso_duration; duration)`
+//!   - truncate with a `…` marker at the `start`, `mid`, or `end` with `s!(ellipsis = mid, max = 10; text)`
+//!   - pluralize a noun by count with `s!(plural 3, "item")` or `s!(plural 2, "mouse", "mice")`
+//!   - mask sensitive values, keeping the first and last characters, with `s!(mask; "password123")`
+//!   - render zero-padded binary of a fixed bit width with `s!(bin_w = 8; 5)`
+//!   - wrap text in ANSI colors and styles with `s!(color bold red; "error")`, or skip them with `s!(color plain; "error")`
+//!   - convert identifiers with `s!(snake; "myVariableName")` and `s!(camel; "my_variable_name")`
+//!   - compute a CRC-32 checksum as 8 hex digits with `s!(crc32; bytes)`
+//!   - draw a Unicode box around (multi-line) text with `s!(frame; "Hello")`
+//!   - join key-value pairs into `"a=1&b=2"` style strings with `s!(kv = "=", sep = "&"; "a" => 1, "b" => 2)`
+//!   - spell out an integer in English words with `s!(words; 42)`
+//!   - zip two lists into `"a=1 b=2"` style pairs with `s!(interleave; [a, b], [1, 2], sep = "=")`
+//!   - format money as `"$1,234.50"` with `s!(money; 1234.5)` or a custom symbol with `s!(money "€"; 1234.5)`
+//!   - build a markdown table with `s!(md_table; headers = ["A", "B"]; ["1", "2"], ["3", "4"])`
+//!   - lay items out in fixed-width columns with `s!(columns = 3, width = 12; items)`
+//!   - render a map as a bordered ASCII table with `s!(map_table; headers = ["Key", "Value"]; map)`
+//!   - render a two-level tree listing with `s!(tree; "root" => ["a", "b" => ["c"]])`
+//!   - wrap text in a `rust`, `hash`, `semicolon`, or `block` comment with `s!(comment rust; "note")`
+//!   - right-align a number in a column with `s!(rnum = 10; 42)`, optionally grouped with `s!(rnum = 12, commas; 1234567)`
+//!   - describe an offset in seconds as `"in 2 hours"` or `"3 days ago"` with `s!(rel_time; seconds)`
+//!   - truncate to a byte budget without splitting a character with `s!(bytes_max = 16; text)`
+//!   - render a bullet list with `s!(bullets; "first", "second")` or a custom marker with `s!(bullets "*"; ...)`
+//!   - draw a Unicode sparkline with `s!(sparkline; [1.0, 3.0, 2.0, 5.0])`
+//!   - line numbers up on their decimal points with `s!(decimal_align = 8; [1.5, 22.25, 3.125])`
+//!   - group digits like a Rust literal (`"1_234_567"`) with `s!(uscore; 1234567)`
+//!   - render escaped HTML attributes with `s!(attrs; "class" => "btn", "id" => "main")`
+//!   - repeat a pattern to fill an exact width with `s!(fill = 20; "-=")`
 //! - `cnct!()` is a wrapper around `s!()` for preferential purposes
 //! - `map()` creates a HashMap with initial values or from a Vec/array of tuple pairs
 //!   - `map!(k1 v1, k1 v2)` | `map!(k1: v1, k2:v2)` | `map!(k1 => v1, k2 => v2)` | `map!(k1 -> v1, k2 -> v2)`
+//!   - Drop entries failing a predicate with `map!(filter |k, v| *v > 0; k1 => v1, k2 => v2)`
+//!   - Build from a collection with key and value accessors: `map!(from items: |x| x.key, |x| x.value)`
+//!   - Compute each value from its key: `map!(keys: [k1, k2], default_fn: |k| k.len())`
+//!   - Skip `None` values with `map!(opt: k1 => Some(v1), k2 => None)`
+//! - `assert_err_matches!()` asserts that a `Result` is an `Err` matching a pattern
+//! - `vec_dedup_by_key!()` keeps the first element for each distinct key, preserving order
+//! - `chained_map!()` builds a `HashMap` then applies optional `map_values` and `filter` stages
+//! - `count_if!()` counts the elements matching a predicate
+//! - `keys_where!()` finds the keys of a `HashMap` whose values match a predicate
+//! - `transpose!()` turns the rows of a rectangular `Vec<Vec<T>>` into columns
+//! - `sorted_map_string!()` renders a `HashMap` as a canonical `"{k1: v1, k2: v2}"` string with sorted keys
+//! - `range_map!()` builds a `RangeMap` for bucketing values by range (e.g. `range_map!(0..10 => "low", 10..20 => "high")`)
+//! - `memoize!()` caches the result of a pure computation per key in a thread-local cache
+//! - `map_reduce!()` maps each item to a value and folds the values with a reducer
+//! - `assert_len!()` asserts that a collection has the expected length
+//! - `window_map!()` maps a closure over every sliding window of a slice
+//! - `nonempty_s!()` builds a string like `s!()` but returns `None` when it comes out empty
+//! - `map_chunks!()` splits a `HashMap` into a number of sub-maps by sorted key
+//! - `clamp_values()` clamps every value of a `HashMap` into `[min, max]`
+//! - `map_to_json!()` serializes a `HashMap` as a JSON object string
+//! - `retry_until!()` retries a producer until its result passes a predicate
+//! - `map_merge_assign!()` merges one map into another in place, optionally resolving conflicts with a closure
+//! - `debug_assert_keys!()` asserts in debug builds that a `HashMap` has every listed key
+//! - `flat_map_vec!()` maps items to iterables and flattens them into a `Vec`
+//! - `value_stats()` computes the count, sum, min, max and mean of a `HashMap`'s numeric values
+//! - `collect_set!()` collects the values a closure produces into a `HashSet`
+//! - `map_intersect!()` keeps the entries of a `HashMap` whose keys are also in another, and `common_keys()` lists those keys
+//! - `map_apply!()` runs a closure for every entry of a `HashMap`
+//! - `build_vec!()` builds a `Vec` from pushes, optionally guarded (e.g. `build_vec!(push 1, if cond => push 2)`)
+//! - `top_n_by_value()` keeps the `n` entries of a `HashMap` with the largest values
+//! - `values_by_key!()` collects the values of a `HashMap` in sorted key order
+//! - `either!()` handles the `Ok` and `Err` sides of a `Result` with a closure each
+//! - `env_prefix_map!()` collects the environment variables with a prefix into a `HashMap`, prefix stripped
+//! - `map_zip!()` pairs up the values of two `HashMap`s by their shared keys, as does the `zip_values()` function
+//! - `chunked_string!()` splits a string into fixed-size character groups joined by a separator
+//! - `map_diff_string!()` describes the added, removed and changed keys between two `HashMap`s
 //! 
 //! # String Creation & Concatenation
 //! ```rust 
@@ -66,6 +131,8 @@ pub mod helpers;
 #[doc(hidden)]
 pub mod macros;
 
+pub use helpers::{clamp_values, common_keys, map_diff, top_n_by_value, value_stats, zip_values, JsonValue, Len, MapDiff, RangeMap, Stats};
+
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
 pub struct ReadmeDoctests;
@@ -5,6 +5,35 @@
 /// - This can also perform concatination.
 /// - Will generate a `String::new()` if no value is passed.
 /// - Automatically insert spaces with a `.` prefix to the arguments: `s!(./*Rest of arguments*/)`
+/// - Fill `{key}` placeholders in a template: `s!(tpl "Hello {name}"; name = "World")`
+/// - Reverse text by Unicode scalar value: `s!(reverse_str; "abc")`
+/// - Render a `Duration` as ISO-8601: `s!(iso_duration; duration)`
+/// - Truncate with an ellipsis at the `start`, `mid`, or `end`: `s!(ellipsis = mid, max = 10; text)`
+/// - Pluralize a noun by its count: `s!(plural 3, "item")` or `s!(plural 2, "mouse", "mice")`
+/// - Mask sensitive values: `s!(mask; "password123")`
+/// - Zero-padded binary of a fixed bit width: `s!(bin_w = 8; 5)`
+/// - Wrap text in ANSI color codes: `s!(color red; "error")` or `s!(color plain; "error")` to skip them
+/// - Convert identifiers between cases: `s!(snake; "myVariableName")` and `s!(camel; "my_variable_name")`
+/// - CRC-32 checksum as 8 hex digits: `s!(crc32; bytes)`
+/// - Draw a box around text: `s!(frame; "Hello")`
+/// - Join key-value pairs: `s!(kv = "="; "a" => 1, "b" => 2)` or `s!(kv = "=", sep = ";"; ...)`
+/// - Spell out an integer in English: `s!(words; 42)`
+/// - Zip two lists into joined pairs: `s!(interleave; [a, b], [1, 2], sep = "=")`
+/// - Format money: `s!(money; 1234.5)` or `s!(money "€"; 1234.5)`
+/// - Build a markdown table: `s!(md_table; headers = ["A", "B"]; ["1", "2"])`
+/// - Lay items out in columns: `s!(columns = 3, width = 12; items)`
+/// - Render a map as an ASCII table: `s!(map_table; headers = ["Key", "Value"]; map)`
+/// - Render a two-level tree: `s!(tree; "root" => ["a", "b" => ["c"]])`
+/// - Wrap text in a code comment: `s!(comment rust; "note")` or `s!(comment block; "note")`
+/// - Right-align a number: `s!(rnum = 10; 42)` or with thousands grouping `s!(rnum = 12, commas; 1234567)`
+/// - Describe a signed offset in seconds: `s!(rel_time; -3 * 86_400)`
+/// - Truncate to a byte budget on a char boundary: `s!(bytes_max = 16; text)`
+/// - Render a bullet list: `s!(bullets; "first", "second")` or `s!(bullets "*"; ...)`
+/// - Draw a sparkline: `s!(sparkline; [1.0, 3.0, 2.0, 5.0])`
+/// - Align numbers on their decimal points: `s!(decimal_align = 8; [1.5, 22.25])`
+/// - Group digits with underscores: `s!(uscore; 1234567)`
+/// - Render HTML attributes: `s!(attrs; "class" => "btn", "id" => "main")`
+/// - Repeat a pattern to fill a width: `s!(fill = 20; "-=")`
 ///
 /// # Examples
 ///
@@ -29,152 +58,1744 @@
 /// let concat_string = s!(.this, "is number", 1, Enum::Value, "macro:", true); // The prefix '.' Tells macro to insert spaces
 /// assert_eq!(concat_string, String::from("This is number 1 value macro: true"));
 /// ```
+///
+/// Templates replace each `{key}` with the matching named argument. Placeholders without a
+/// matching argument are left in the output untouched.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(tpl "Hello {name}"; name = "World"), "Hello World");
+/// assert_eq!(s!(tpl "{greeting}, {name}!"; greeting = "Hi", name = 42), "Hi, 42!");
+/// assert_eq!(s!(tpl "Hello {name}"; user = "World"), "Hello {name}"); // Unmatched stays literal
+/// ```
+///
+/// `reverse_str` reverses the text by Unicode scalar values (`char`s), not by grapheme clusters,
+/// so combining marks end up attached to the preceding character in the reversed output.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(reverse_str; "abc"), "cba");
+/// assert_eq!(s!(reverse_str; "e\u{301}x"), "x\u{301}e"); // The combining accent moves with its scalar
+/// ```
+///
+/// `iso_duration` renders a `std::time::Duration` as an ISO-8601 duration. Zero-valued units are
+/// skipped, with a zero duration rendered as `"PT0S"`, and sub-second parts are kept as a fraction.
+///
+/// ```
+/// use simplicio::s;
+/// use std::time::Duration;
+///
+/// assert_eq!(s!(iso_duration; Duration::from_secs(3723)), "PT1H2M3S");
+/// assert_eq!(s!(iso_duration; Duration::from_secs(7200)), "PT2H");
+/// assert_eq!(s!(iso_duration; Duration::from_millis(2250)), "PT2.25S");
+/// assert_eq!(s!(iso_duration; Duration::from_secs(0)), "PT0S");
+/// ```
+///
+/// `ellipsis` shortens text to `max` characters and marks the cut with `…` at the `start`, `mid`,
+/// or `end`. `max` counts the characters kept from the original, the marker is added on top of it,
+/// and text that already fits is returned unchanged.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(ellipsis = start, max = 10; "verylongfilename.txt"), "…lename.txt");
+/// assert_eq!(s!(ellipsis = mid, max = 10; "verylongfilename.txt"), "veryl…e.txt");
+/// assert_eq!(s!(ellipsis = end, max = 10; "verylongfilename.txt"), "verylongfi…");
+/// assert_eq!(s!(ellipsis = end, max = 3; "héllo"), "hél…"); // Never splits a character
+/// ```
+///
+/// `plural` prefixes a noun with its count, using the plural form for any count other than one.
+/// Regular plurals are derived automatically and an irregular plural can be passed as a third value.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(plural 1, "item"), "1 item");
+/// assert_eq!(s!(plural 3, "item"), "3 items");
+/// assert_eq!(s!(plural 2, "mouse", "mice"), "2 mice");
+/// ```
+///
+/// `mask` hides the middle of a sensitive value behind `*`, keeping only the first and last
+/// characters. Values of two characters or fewer are masked completely.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(mask; "password123"), "p*********3");
+/// assert_eq!(s!(mask; "ok"), "**");
+/// ```
+///
+/// `bin_w` renders an integer in binary, zero-padded to the given bit width. Values that need more
+/// bits than the width are rendered in full rather than cut off.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(bin_w = 8; 5), "00000101");
+/// assert_eq!(s!(bin_w = 4; 0b1_0110), "10110"); // Wider than 4 bits, so nothing is dropped
+/// ```
+///
+/// `color` wraps the text in ANSI escape codes for terminal output. It takes one or more of the
+/// colors `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white` and the styles
+/// `bold`, `dim`, `italic`, `underline`. `color plain` leaves the text untouched, for turning colors
+/// off without changing the call. The names are checked at compile time, so a typo is a compile
+/// error rather than a panic.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(color red; "error"), "\x1b[31merror\x1b[0m");
+/// assert_eq!(s!(color bold green; "ok"), "\x1b[1;32mok\x1b[0m");
+/// assert_eq!(s!(color plain; "error"), "error");
+/// ```
+///
+/// ```compile_fail
+/// use simplicio::s;
+///
+/// let text = s!(color rde; "error"); // Fails to compile: unknown color or style `rde`
+/// ```
+///
+/// `snake` and `camel` convert identifiers between `snake_case` and `camelCase`. Acronyms are
+/// treated as a single word and leading underscores are preserved. Since acronyms are lowercased on
+/// the way to `snake_case`, converting `"parseJSON"` back gives `"parseJson"`.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(snake; "myVariableName"), "my_variable_name");
+/// assert_eq!(s!(camel; "my_variable_name"), "myVariableName");
+/// assert_eq!(s!(camel; s!(snake; "userId")), "userId"); // Round trip
+/// assert_eq!(s!(snake; s!(camel; "_row_count")), "_row_count"); // Round trip
+/// assert_eq!(s!(snake; "HTTPServer"), "http_server");
+/// ```
+///
+/// `crc32` computes the CRC-32 checksum of a string or bytes, rendered as 8 lowercase hex digits.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(crc32; "123456789"), "cbf43926");
+/// assert_eq!(s!(crc32; b"123456789"), "cbf43926");
+/// assert_eq!(s!(crc32; vec![0u8; 0]), "00000000");
+/// ```
+///
+/// `frame` draws a box around the text with Unicode box-drawing characters, sized to the longest
+/// line. Multi-line text gets one row per line.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(frame; "Hello"), "┌───────┐\n│ Hello │\n└───────┘");
+/// assert_eq!(
+///     s!(frame; "Hello\nWorld!"),
+///     "┌────────┐\n│ Hello  │\n│ World! │\n└────────┘"
+/// );
+/// ```
+///
+/// `kv` joins key-value pairs into an assignment string, with the separator given to `kv` placed
+/// between each key and value. Pairs are joined with `&` unless another separator is passed with `sep`.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(kv = "="; "a" => 1, "b" => 2), "a=1&b=2"); // Query style
+/// assert_eq!(s!(kv = "=", sep = ";"; "HOME" => "/root", "LANG" => "C"), "HOME=/root;LANG=C"); // Env style
+/// assert_eq!(s!(kv = ": ", sep = ", "; "x" => 1.5, "y" => true), "x: 1.5, y: true");
+/// ```
+///
+/// `words` spells out an integer of any primitive integer type in English, prefixing negative
+/// numbers with `"negative "`.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(words; 0), "zero");
+/// assert_eq!(s!(words; 7), "seven");
+/// assert_eq!(s!(words; 42), "forty-two");
+/// assert_eq!(s!(words; 100), "one hundred");
+/// assert_eq!(s!(words; 1234), "one thousand two hundred thirty-four");
+/// assert_eq!(s!(words; -9999), "negative nine thousand nine hundred ninety-nine");
+///
+/// let items = vec!["a", "b", "c"];
+/// assert_eq!(s!(words; items.len()), "three");
+/// let n: u32 = 12;
+/// assert_eq!(s!(words; n), "twelve");
+/// assert!(s!(words; u128::MAX).starts_with("three hundred forty undecillion"));
+/// ```
+///
+/// `interleave` zips two lists together, joining each pair with `sep` and the pairs with spaces.
+/// Each list can be anything iterable whose items implement `Display`, and extra items in the longer
+/// list are dropped.
+///
+/// ```
+/// use simplicio::s;
+///
+/// let (a, b, c) = ("a", "b", "c");
+/// assert_eq!(s!(interleave; [a, b, c], [1, 2, 3], sep = "="), "a=1 b=2 c=3");
+/// assert_eq!(s!(interleave; vec!["x", "y"], [0.5, 1.5], sep = ": "), "x: 0.5 y: 1.5");
+/// ```
+///
+/// `money` formats an amount with thousands separators and two decimals, behind a `$` or the given
+/// currency symbol. Integer amounts of any type are whole units, floats are rounded to the cent, and
+/// a `NaN` or infinite float panics.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(money; 123456.7), "$123,456.70");
+/// assert_eq!(s!(money "€"; 1234.5), "€1,234.50");
+/// assert_eq!(s!(money; -42), "-$42.00");
+///
+/// let balance: u64 = 1_500_000;
+/// assert_eq!(s!(money; balance), "$1,500,000.00");
+/// ```
+///
+/// `md_table` builds a GitHub-flavored markdown table from the `headers` and the rows that follow
+/// them. `|` in a cell is escaped and line breaks become `<br>`. It panics if there are no headers
+/// or if a row does not have one cell per header.
+///
+/// ```
+/// use simplicio::s;
+///
+/// let table = s!(md_table; headers = ["A", "B"]; ["1", "2"], ["3", "4"]);
+/// assert_eq!(table, "| A | B |\n| --- | --- |\n| 1 | 2 |\n| 3 | 4 |");
+/// assert_eq!(s!(md_table; headers = ["A"]; ["x\ny"]), "| A |\n| --- |\n| x<br>y |");
+/// ```
+///
+/// `columns` and `width` lay a list of items out in columns, filling each row from left to right
+/// before starting the next one. Items are padded to the column width, cells are separated by one
+/// space, and trailing spaces are trimmed. An item as wide as the column or wider is kept whole.
+///
+/// ```
+/// use simplicio::s;
+///
+/// let items = vec!["alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta"];
+/// assert_eq!(
+///     s!(columns = 3, width = 7; items),
+///     "alpha   beta    gamma\ndelta   epsilon zeta\neta"
+/// );
+///
+/// assert_eq!(s!(columns = 2, width = 5; vec!["abcde", "x", "toolongitem", "y"]), "abcde x\ntoolongitem y");
+/// ```
+///
+/// `map_table` renders a map as a bordered ASCII table with the given `headers`, one row per entry
+/// in sorted key order. Keys must implement `Ord` and both keys and values `ToString`.
+///
+/// ```
+/// use simplicio::{map, s};
+///
+/// let stock = map!("pear" => 7, "apple" => 12, "fig" => 150);
+/// assert_eq!(
+///     s!(map_table; headers = ["Key", "Value"]; stock),
+///     "+-------+-------+\n\
+///      | Key   | Value |\n\
+///      +-------+-------+\n\
+///      | apple | 12    |\n\
+///      | fig   | 150   |\n\
+///      | pear  | 7     |\n\
+///      +-------+-------+"
+/// );
+/// ```
+///
+/// `tree` renders a root with its children as an indented tree. Each child can list children of its
+/// own with `=> [...]`, for up to two levels below the root.
+///
+/// ```
+/// use simplicio::s;
+///
+/// let tree = s!(tree; "root" => ["a", "b" => ["c", "d"], "e"]);
+/// assert_eq!(tree, "root\n├── a\n├── b\n│   ├── c\n│   └── d\n└── e");
+/// ```
+///
+/// `comment` turns text into a source code comment: `rust` for `//`, `hash` for `#`, `semicolon` for
+/// `;`, and `block` for `/* */`. Multi-line text gets the prefix on every line, or a ` * ` gutter
+/// inside a block comment. Empty text and empty lines get the marker alone, with no trailing space.
+/// In a `block` comment any `*/` in the text becomes `* /` so it cannot end the comment early, and
+/// a single trailing newline is dropped.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(comment rust; "note"), "// note");
+/// assert_eq!(s!(comment hash; "first\nsecond"), "# first\n# second");
+/// assert_eq!(s!(comment block; "note"), "/* note */");
+/// assert_eq!(s!(comment block; "first\nsecond"), "/*\n * first\n * second\n */");
+///
+/// // Empty text and empty lines get the bare marker, other text is kept as written
+/// assert_eq!(s!(comment rust; ""), "//");
+/// assert_eq!(s!(comment block; ""), "/* */");
+/// assert_eq!(s!(comment hash; "a\n\nb  "), "# a\n#\n# b  ");
+/// assert_eq!(s!(comment block; "a\n\nb"), "/*\n * a\n *\n * b\n */");
+///
+/// assert_eq!(s!(comment block; "a */ b"), "/* a * / b */");
+/// assert_eq!(s!(comment block; "a\n"), "/* a */");
+/// ```
+///
+/// `rnum` right-aligns a number within the given width, for report columns. Adding `commas` groups
+/// the digits by thousands first. Numbers wider than the column are kept whole.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(rnum = 10; 42), "        42");
+/// assert_eq!(s!(rnum = 12, commas; 1234567), "   1,234,567");
+/// assert_eq!(s!(rnum = 12, commas; -9876.5), "    -9,876.5");
+/// ```
+///
+/// `rel_time` describes a signed offset in seconds as a relative time: positive offsets are in the
+/// future, negative ones in the past, and anything under ten seconds is `"just now"`.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(rel_time; 2 * 3600), "in 2 hours");
+/// assert_eq!(s!(rel_time; -3 * 86_400), "3 days ago");
+/// assert_eq!(s!(rel_time; -45), "45 seconds ago");
+/// assert_eq!(s!(rel_time; 3), "just now");
+/// ```
+///
+/// `bytes_max` truncates text to fit in at most that many bytes, as with fixed-size buffers. A
+/// multibyte character that would be cut in half is dropped entirely.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(bytes_max = 16; "The quick brown fox"), "The quick brown ");
+/// assert_eq!(s!(bytes_max = 16; "short"), "short");
+/// assert_eq!(s!(bytes_max = 11; "naïve café"), "naïve caf"); // `é` needs bytes 11 and 12
+/// assert_eq!(s!(bytes_max = 3; "naïve café"), "na"); // `ï` needs bytes 3 and 4
+/// ```
+///
+/// `bullets` renders each value as a line of a bullet list, marked with `-` unless another marker is
+/// given.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(bullets; "first", "second", "third"), "- first\n- second\n- third");
+/// assert_eq!(s!(bullets "*"; "first", 2), "* first\n* 2");
+/// ```
+///
+/// `sparkline` draws a tiny inline chart with one block character per value, scaled between the
+/// smallest and largest values. Data where every value is equal is drawn at a middle level.
+///
+/// ```
+/// use simplicio::s;
+///
+/// let data = [1.0, 3.0, 2.0, 5.0];
+/// let line = s!(sparkline; data);
+/// assert_eq!(line.chars().count(), data.len());
+/// assert_eq!(line, "▁▅▃█");
+/// assert_eq!(s!(sparkline; vec![4.0, 4.0, 4.0]), "▄▄▄");
+/// ```
+///
+/// `decimal_align` puts each number on its own line with the decimal points lined up, right-aligned
+/// within the given width.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(
+///     s!(decimal_align = 8; [1.5, 22.25, 3.125]),
+///     "   1.5  \n  22.25 \n   3.125"
+/// );
+/// assert_eq!(s!(decimal_align = 6; [12, 3]), "    12\n     3"); // Integers align on the ones digit
+/// ```
+///
+/// `uscore` groups the digits of a number by threes with underscores, like a Rust literal.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(uscore; 1234567), "1_234_567");
+/// assert_eq!(s!(uscore; -9876543210i64), "-9_876_543_210");
+/// assert_eq!(s!(uscore; 512), "512");
+/// ```
+///
+/// `attrs` renders name-value pairs as HTML attributes, each with a leading space so the result can
+/// go straight after a tag name. Values are HTML-escaped, and no pairs give an empty string.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(attrs; "class" => "btn", "id" => "main"), " class=\"btn\" id=\"main\"");
+/// assert_eq!(s!(attrs; "title" => "Say \"hi\" & <wave>"), " title=\"Say &quot;hi&quot; &amp; &lt;wave&gt;\"");
+/// assert_eq!(s!(attrs;), "");
+/// ```
+///
+/// `fill` repeats a pattern to fill exactly the given number of characters, cutting the last repeat
+/// short when it does not fit. A pattern longer than the width is truncated to it.
+///
+/// ```
+/// use simplicio::s;
+///
+/// assert_eq!(s!(fill = 20; "-="), "-=-=-=-=-=-=-=-=-=-="); // Exact fit
+/// assert_eq!(s!(fill = 7; "abc"), "abcabca"); // Partial last repeat
+/// assert_eq!(s!(fill = 4; "=========="), "===="); // Pattern longer than the width
+/// ```
+#[macro_export]
+macro_rules! s {
+    //Default input
+    ($($e:expr),* $(,)?) => {
+        {
+            let mut string: String = String::new();
+            $(
+                let add: &str = &$e.to_string();
+                string.push_str(add);
+            )*
+            string
+        }
+    };
+
+    //Automatic spacing concatination
+    (.$($e:expr),* $(,)?) => {
+        {
+            let mut string: String = String::new();
+            let mut first = true;
+            $(
+                if !first { string.push(' '); } else { first = false; }
+                let add: &str = &$e.to_string();
+                string.push_str(add);
+            )*
+            string
+        }
+    };
+
+    //Template substitution
+    (tpl $tpl:expr; $($key:ident = $val:expr),* $(,)?) => {
+        $crate::helpers::substitute(&$tpl.to_string(), &[$((stringify!($key), $val.to_string())),*])
+    };
+
+    //Reverse by Unicode scalar value
+    (reverse_str; $e:expr) => {
+        $e.to_string().chars().rev().collect::<String>()
+    };
+
+    //ISO-8601 duration
+    (iso_duration; $e:expr) => {
+        $crate::helpers::iso_duration($e)
+    };
+
+    //Truncation with a positioned ellipsis
+    (ellipsis = start, max = $max:expr; $e:expr) => {
+        $crate::helpers::ellipsize(&$e.to_string(), $max, $crate::helpers::Ellipsis::Start)
+    };
+    (ellipsis = mid, max = $max:expr; $e:expr) => {
+        $crate::helpers::ellipsize(&$e.to_string(), $max, $crate::helpers::Ellipsis::Mid)
+    };
+    (ellipsis = end, max = $max:expr; $e:expr) => {
+        $crate::helpers::ellipsize(&$e.to_string(), $max, $crate::helpers::Ellipsis::End)
+    };
+
+    //Count with a pluralized noun
+    (plural $n:expr, $singular:expr $(,)?) => {
+        $crate::helpers::pluralize($n, &$singular.to_string(), None)
+    };
+    (plural $n:expr, $singular:expr, $plural:expr $(,)?) => {
+        $crate::helpers::pluralize($n, &$singular.to_string(), Some(&$plural.to_string()))
+    };
+
+    //Mask all but the first and last characters
+    (mask; $e:expr) => {
+        $crate::helpers::mask(&$e.to_string())
+    };
+
+    //Fixed-width binary
+    (bin_w = $width:expr; $e:expr) => {
+        format!("{:0width$b}", $e, width = $width)
+    };
+
+    //ANSI colors and styles
+    (color plain; $e:expr) => {
+        $e.to_string()
+    };
+    (color $($style:ident)+; $e:expr) => {
+        $crate::helpers::colorize(&[$($crate::s!(@ansi $style)),+], &$e.to_string())
+    };
+
+    //SGR code for a color or style name
+    (@ansi bold) => { "1" };
+    (@ansi dim) => { "2" };
+    (@ansi italic) => { "3" };
+    (@ansi underline) => { "4" };
+    (@ansi black) => { "30" };
+    (@ansi red) => { "31" };
+    (@ansi green) => { "32" };
+    (@ansi yellow) => { "33" };
+    (@ansi blue) => { "34" };
+    (@ansi magenta) => { "35" };
+    (@ansi cyan) => { "36" };
+    (@ansi white) => { "37" };
+    (@ansi $other:ident) => {
+        compile_error!(concat!("s!(color ...): unknown color or style `", stringify!($other), "`"))
+    };
+
+    //Identifier case conversion
+    (snake; $e:expr) => {
+        $crate::helpers::to_snake_case(&$e.to_string())
+    };
+    (camel; $e:expr) => {
+        $crate::helpers::to_camel_case(&$e.to_string())
+    };
+
+    //CRC-32 checksum as hex
+    (crc32; $e:expr) => {
+        format!("{:08x}", $crate::helpers::crc32(&$e))
+    };
+
+    //Box-drawn frame
+    (frame; $e:expr) => {
+        $crate::helpers::frame(&$e.to_string())
+    };
+
+    //Key-value pairs joined into an assignment string
+    (kv = $kv:expr; $($key:expr => $val:expr),* $(,)?) => {
+        $crate::s!(kv = $kv, sep = "&"; $($key => $val),*)
+    };
+    (kv = $kv:expr, sep = $sep:expr; $($key:expr => $val:expr),* $(,)?) => {
+        {
+            let kv = $kv.to_string();
+            let pairs: Vec<String> = vec![$(format!("{}{}{}", $key, kv, $val)),*];
+            pairs.join(&$sep.to_string())
+        }
+    };
+
+    //Integer spelled out in English
+    (words; $e:expr) => {
+        $crate::helpers::number_to_words($e)
+    };
+
+    //Two lists zipped into pairs
+    (interleave; $left:expr, $right:expr, sep = $sep:expr $(,)?) => {
+        {
+            let sep = $sep.to_string();
+            let pairs: Vec<String> = $left
+                .into_iter()
+                .zip($right)
+                .map(|(left, right)| format!("{}{}{}", left, sep, right))
+                .collect();
+            pairs.join(" ")
+        }
+    };
+
+    //Currency formatting
+    (money; $e:expr) => {
+        $crate::helpers::format_money($e, "$")
+    };
+    (money $symbol:expr; $e:expr) => {
+        $crate::helpers::format_money($e, &$symbol.to_string())
+    };
+
+    //Markdown table
+    (md_table; headers = [$($header:expr),* $(,)?]; $([$($cell:expr),* $(,)?]),* $(,)?) => {
+        $crate::helpers::markdown_table(&[$($header.to_string()),*], &[$(vec![$($cell.to_string()),*]),*])
+    };
+
+    //Items laid out in columns
+    (columns = $columns:expr, width = $width:expr; $e:expr) => {
+        $crate::helpers::columns($e, $columns, $width)
+    };
+
+    //ASCII table of a map's sorted entries
+    (map_table; headers = [$key:expr, $value:expr $(,)?]; $map:expr) => {
+        {
+            let rows: Vec<Vec<String>> = $crate::helpers::sorted_entries(&$map)
+                .into_iter()
+                .map(|(key, value)| vec![key.to_string(), value.to_string()])
+                .collect();
+            $crate::helpers::ascii_table(&[$key.to_string(), $value.to_string()], &rows)
+        }
+    };
+
+    //Two-level tree listing
+    (tree; $root:expr => [$($child:expr $(=> [$($leaf:expr),* $(,)?])?),* $(,)?]) => {
+        $crate::helpers::render_tree(
+            &$root.to_string(),
+            &[$(($child.to_string(), vec![$($($leaf.to_string()),*)?])),*],
+        )
+    };
+
+    //Text wrapped in a code comment
+    (comment rust; $e:expr) => { $crate::s!(@line_comment "//", $e) };
+    (comment hash; $e:expr) => { $crate::s!(@line_comment "#", $e) };
+    (comment semicolon; $e:expr) => { $crate::s!(@line_comment ";", $e) };
+    (comment block; $e:expr) => {
+        {
+            let text = $e.to_string().replace("*/", "* /");
+            let text = text.strip_suffix('\n').map_or(text.as_str(), |rest| rest.strip_suffix('\r').unwrap_or(rest));
+            if text.contains('\n') {
+                let lines: Vec<String> = text
+                    .lines()
+                    .map(|line| if line.is_empty() { String::from(" *") } else { format!(" * {}", line) })
+                    .collect();
+                format!("/*\n{}\n */", lines.join("\n"))
+            } else if text.is_empty() {
+                String::from("/* */")
+            } else {
+                format!("/* {} */", text)
+            }
+        }
+    };
+
+    //Prefixes every line of the text with a line comment marker, empty lines getting the bare marker
+    (@line_comment $prefix:expr, $e:expr) => {
+        {
+            let text = $e.to_string();
+            let mut lines: Vec<&str> = text.lines().collect();
+            if lines.is_empty() {
+                lines.push("");
+            }
+            lines
+                .into_iter()
+                .map(|line| if line.is_empty() { $prefix.to_string() } else { format!("{} {}", $prefix, line) })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    };
+
+    //Right-aligned number column
+    (rnum = $width:expr; $e:expr) => {
+        format!("{:>width$}", $e.to_string(), width = $width)
+    };
+    (rnum = $width:expr, commas; $e:expr) => {
+        format!("{:>width$}", $crate::helpers::group_digits(&$e.to_string(), ","), width = $width)
+    };
+
+    //Relative time phrase
+    (rel_time; $e:expr) => {
+        $crate::helpers::relative_time($e)
+    };
+
+    //Truncation to a byte budget
+    (bytes_max = $max:expr; $e:expr) => {
+        $crate::helpers::truncate_bytes(&$e.to_string(), $max).to_string()
+    };
+
+    //Bullet list
+    (bullets; $($e:expr),* $(,)?) => {
+        $crate::s!(bullets "-"; $($e),*)
+    };
+    (bullets $marker:expr; $($e:expr),* $(,)?) => {
+        {
+            let marker = $marker.to_string();
+            let lines: Vec<String> = vec![$(format!("{} {}", marker, $e)),*];
+            lines.join("\n")
+        }
+    };
+
+    //Unicode block sparkline
+    (sparkline; $e:expr) => {
+        $crate::helpers::sparkline(&$e)
+    };
+
+    //Numbers aligned on their decimal points
+    (decimal_align = $width:expr; $e:expr) => {
+        $crate::helpers::decimal_align($e, $width)
+    };
+
+    //Digits grouped with underscores
+    (uscore; $e:expr) => {
+        $crate::helpers::group_digits(&$e.to_string(), "_")
+    };
+
+    //HTML attributes
+    (attrs; $($name:expr => $val:expr),* $(,)?) => {
+        {
+            let mut attrs = String::new();
+            $(
+                attrs.push_str(&format!(" {}=\"{}\"", $name, $crate::helpers::html_escape(&$val.to_string())));
+            )*
+            attrs
+        }
+    };
+
+    //Pattern repeated to fill a width
+    (fill = $width:expr; $e:expr) => {
+        $e.to_string().chars().cycle().take($width).collect::<String>()
+    };
+}
+
+/// Concatenates multiple string slices and/or `String` objects.
+///
+/// A wrapper for the `s!()` macro that allows people to assign purpose to one for ease of readability.
+///
+/// # Examples
+///
+/// ```no_run
+/// use simplicio::cnct;
+///
+/// // Creating an enum for example purposes
+/// enum Enum { Value }  //Create the enum, Enum
+/// impl std::fmt::Display for Enum {  // Implement the Display trait
+///    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///        match self { Enum::Value => write!(f, "value"), }
+///    }
+/// }
+///
+/// let concatenated_string = cnct!("Yel", "low", "!");
+/// assert_eq!(concatenated_string, String::from("Yellow!"));
+///
+/// let text = String::from("this");
+/// let concat_string = cnct!(.text, true, 123, Enum::Value); //Assuming Enum implements the ToString trait and is set to "Value"
+/// assert_eq!(concat_string, String::from("this true 123 value"));
+/// ```
+#[macro_export]
+macro_rules! cnct {
+    //Default case
+    ($($e:expr),* $(,)?) => {
+        $crate::s!($($e),*);
+    };
+
+    //Automatic spacing
+    (.$($e:expr),* $(,)?) => {
+        $crate::s!(.$($e),*);
+    };
+}
+
+/// Creates a `HashMap` from a list of key-value pairs.
+///
+/// This macro simplifies the creation of a `HashMap` from a series
+/// of key-value pairs. It's a convenient way to initialize a `HashMap`
+/// without manually calling `insert` for each pair.
+///
+/// # Examples
+///
+/// ```no_run
+/// use simplicio::map;
+/// 
+/// // Create the HashMap test_map = {"k1": "v1", "k2": "v2"}
+/// let mut test_map = std::collections::HashMap::new(); 
+/// test_map.insert("k1", "v1");
+/// test_map.insert("k2", "v2");
+///
+/// let mut hashmaps: Vec<std::collections::HashMap<&str, &str>> = vec![]; // Holds all variants of map!()
+/// let vecmap = vec![("k1", "v1"), ("k2", "v2")]; // Vector of key/value tuples
+/// let arrmap = [("k1", "v1"), ("k2", "v2")]; // Array of key/value tuples
+/// hashmaps.push( map!(vecmap) );  // Can insert a vector
+/// hashmaps.push( map!(arrmap) );  // Can insert an array
+/// hashmaps.push( map!("k1" "v1", "k2" "v2") );     // Direct insert: uses `' '` 
+/// hashmaps.push( map!("k1": "v1", "k2": "v2") );   // Direct insert: uses `:` 
+/// hashmaps.push( map!("k1"->"v1", "k2"->"v2") );   // Direct insert: uses `->` 
+/// hashmaps.push( map!("k1"=>"v1", "k2"=>"v2") );   // Direct insert: uses `=>` 
+/// hashmaps.push( map!("k1"["v1"], "k2"["v2"]) ); // Direct insert: uses `[]` to annotate a value
+///
+/// assert!(hashmaps.iter().all(|map| map == &test_map)); // Assert that all cases are true
+/// ```
+///
+/// Entries can be filtered as the map is built by passing a predicate with the `filter` prefix.
+/// The predicate receives each key and value and only the entries it returns `true` for remain.
+///
+/// ```
+/// use simplicio::map;
+///
+/// let positives = map!(filter |_, v| *v > 0; "a" => 1, "b" => -1, "c" => 3);
+/// assert_eq!(positives, map!("a" => 1, "c" => 3));
+/// assert!(!positives.contains_key("b"));
+/// ```
+///
+/// The `from` prefix builds a map out of a collection of items, with one closure picking the key
+/// and another the value of each item. Both closures receive a reference to the item. Wrap the items
+/// in parentheses when they are more than a single variable.
+///
+/// ```
+/// use simplicio::map;
+///
+/// struct Setting { key: &'static str, value: u32 }
+///
+/// let settings = vec![Setting { key: "width", value: 80 }, Setting { key: "depth", value: 3 }];
+/// let by_key = map!(from settings: |s| s.key, |s| s.value);
+/// assert_eq!(by_key, map!("width" => 80, "depth" => 3));
+///
+/// let doubled = map!(from (vec![1, 2, 3].into_iter()): |n| *n, |n| n * 2);
+/// assert_eq!(doubled, map!(1 => 2, 2 => 4, 3 => 6));
+/// ```
+///
+/// `keys` and `default_fn` build a map from a list of keys, computing each value from its key.
+///
+/// ```
+/// use simplicio::map;
+///
+/// let lengths = map!(keys: ["a", "bb", "ccc"], default_fn: |k| k.len());
+/// assert_eq!(lengths, map!("a" => 1, "bb" => 2, "ccc" => 3));
+/// ```
+///
+/// The `opt` prefix takes `Option` values and only inserts the entries that are `Some`, unwrapping
+/// them, so `None` entries are left out of the map entirely.
+///
+/// ```
+/// use simplicio::map;
+///
+/// let config = map!(opt: "a" => Some(1), "b" => None, "c" => Some(3));
+/// assert_eq!(config, map!("a" => 1, "c" => 3));
+/// assert!(!config.contains_key("b"));
+/// ```
+#[macro_export]
+macro_rules! map {
+    // For new
+    () => { std::collections::HashMap::new() };
+
+    // filter |k, v| predicate; entries
+    (filter $predicate:expr; $($entries:tt)+) => {
+        {
+            let mut map = $crate::map!($($entries)+);
+            map.retain($predicate);
+            map
+        }
+    };
+
+    // from items: |x| key, |x| value
+    (from $items:tt : $key:expr, $value:expr $(,)?) => {
+        $crate::helpers::map_by($items, $key, $value)
+    };
+
+    // keys: [k1, k2], default_fn: |k| value
+    (keys: $keys:expr, default_fn: $value:expr $(,)?) => {
+        $crate::helpers::map_from_keys($keys, $value)
+    };
+
+    // opt: k1 => Some(v1), k2 => None
+    (opt: $($key:expr => $val:expr),* $(,)?) => {
+        {
+            let mut map = std::collections::HashMap::new();
+            $(
+                if let Some(value) = $val {
+                    map.insert($key, value);
+                }
+            )*
+            map
+        }
+    };
+
+    // key[value]
+    ($($key:path[$val:expr]),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+    ($($key:tt[$val:expr]),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+
+    // key value || key: value || key -> value || key => value
+    ($($key:tt$(:)?$(->)?$(=>)?$val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+
+    // When keys are paths and not tokens
+    ($($key:path : $val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+    ($($key:path => $val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+
+    // Vec<(_,_)>, &[(_,_)], or manually input map!([(k,v)])
+    ($($arr:expr),+ $(,)?) => {
+        {
+            let mut map = map!();
+            $(
+                map.extend($crate::helpers::mapper($arr));
+            )+
+            map 
+        }
+    };
+
+    //Does the bulk of the mapping
+    (@mapper $($key:expr, $value:expr),+ $(,)?) => {
+        {
+            let mut map = std::collections::HashMap::new();
+            $(
+                map.insert($key, $value);
+            )+
+            map
+        }
+    }
+}
+
+/// Prints to the console with a newline.
+/// Convenient shorthand for `println!`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use simplicio::ln;
+/// ln!();
+/// ```
+#[macro_export]
+macro_rules! ln {
+    () => { println!() };
+}
+
+/// Asserts that a `Result` is an `Err` whose error matches the given pattern.
+///
+/// Panics if the result is `Ok`, or if the error does not match the pattern. The error type
+/// must implement `Debug` so it can be shown in the panic message. An optional `if` guard can
+/// be added after the pattern, just like with `matches!`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::assert_err_matches;
+///
+/// #[derive(Debug)]
+/// enum MyError { NotFound, Invalid(u8) }
+///
+/// let result: Result<(), MyError> = Err(MyError::NotFound);
+/// assert_err_matches!(result, MyError::NotFound);
+///
+/// let result: Result<(), MyError> = Err(MyError::Invalid(3));
+/// assert_err_matches!(result, MyError::Invalid(code) if *code > 2);
+/// ```
+///
+/// ```should_panic
+/// use simplicio::assert_err_matches;
+///
+/// #[derive(Debug)]
+/// enum MyError { NotFound, Invalid(u8) }
+///
+/// let result: Result<(), MyError> = Err(MyError::Invalid(3));
+/// assert_err_matches!(result, MyError::NotFound); // Panics: wrong variant
+/// ```
+#[macro_export]
+macro_rules! assert_err_matches {
+    ($result:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        match &$result {
+            Err(err) => {
+                if !matches!(err, $pattern $(if $guard)?) {
+                    panic!(
+                        "assertion failed: `Err({:?})` does not match `{}`",
+                        err,
+                        stringify!($pattern $(if $guard)?)
+                    );
+                }
+            }
+            Ok(_) => panic!(
+                "assertion failed: expected `Err` matching `{}`, got `Ok(..)`",
+                stringify!($pattern $(if $guard)?)
+            ),
+        }
+    };
+}
+
+/// Deduplicates a collection by a derived key, keeping the first element for each key.
+///
+/// The original order of the kept elements is preserved. Works with anything that can be
+/// turned into an iterator, and the key only needs to implement `Hash` and `Eq`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::vec_dedup_by_key;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct User { id: u32, name: &'static str }
+///
+/// let users = vec![
+///     User { id: 1, name: "Ann" },
+///     User { id: 2, name: "Bob" },
+///     User { id: 1, name: "Ann (again)" },
+///     User { id: 3, name: "Cid" },
+/// ];
+///
+/// let unique = vec_dedup_by_key!(users, |user| user.id);
+/// let names: Vec<&str> = unique.iter().map(|user| user.name).collect();
+/// assert_eq!(names, vec!["Ann", "Bob", "Cid"]);
+/// ```
+#[macro_export]
+macro_rules! vec_dedup_by_key {
+    ($vec:expr, $key:expr $(,)?) => {
+        $crate::helpers::dedup_by_key($vec, $key)
+    };
+}
+
+/// Builds a `HashMap` and runs it through optional transform and filter stages.
+///
+/// - `base` takes anything `map!()` accepts, wrapped in braces.
+/// - `map_values` transforms every value.
+/// - `filter` keeps only the entries the predicate returns `true` for.
+///
+/// The stages run in that order and both `map_values` and `filter` can be left out.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{chained_map, map};
+///
+/// let scores = chained_map!(
+///     base: {"a" => 1, "b" => 5, "c" => 20},
+///     map_values: |v| v * 10,
+///     filter: |_, v| *v < 100,
+/// );
+/// assert_eq!(scores, map!("a" => 10, "b" => 50));
+///
+/// let plain = chained_map!(base: {"a" => 1});
+/// assert_eq!(plain, map!("a" => 1));
+/// ```
+#[macro_export]
+macro_rules! chained_map {
+    (base: {$($base:tt)*} $(, map_values: $transform:expr)? $(, filter: $filter:expr)? $(,)?) => {
+        {
+            let map = $crate::map!($($base)*);
+            $(
+                let map = $crate::helpers::map_values(map, $transform);
+            )?
+            $(
+                let mut map = map;
+                map.retain($filter);
+            )?
+            map
+        }
+    };
+}
+
+/// Counts the elements of a collection that satisfy a predicate.
+///
+/// Works with anything that can be turned into an iterator. The predicate receives a reference
+/// to each element, like `Iterator::filter`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::count_if;
+///
+/// let values = vec![3, -1, 4, -1, 5, 0];
+/// assert_eq!(count_if!(values, |x| *x > 0), 3);
+/// ```
+#[macro_export]
+macro_rules! count_if {
+    ($iter:expr, $predicate:expr $(,)?) => {
+        $iter.into_iter().filter($predicate).count()
+    };
+}
+
+/// Finds every key in a `HashMap` whose value satisfies a predicate.
+///
+/// Returns a `Vec<&K>` in the map's iteration order, so sort it if the order matters.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{keys_where, map};
+///
+/// let owners = map!("ann" => "cat", "bob" => "dog", "cid" => "cat");
+/// let target = "cat";
+///
+/// let mut cat_owners = keys_where!(owners, |pet| *pet == target);
+/// cat_owners.sort();
+/// assert_eq!(cat_owners, vec![&"ann", &"cid"]);
+/// ```
+#[macro_export]
+macro_rules! keys_where {
+    ($map:expr, $predicate:expr $(,)?) => {
+        $crate::helpers::keys_where(&$map, $predicate)
+    };
+}
+
+/// Transposes a `Vec<Vec<T>>`, turning its rows into columns.
+///
+/// The input must be rectangular. A row whose length differs from the first row causes a panic
+/// naming the offending row.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::transpose;
+///
+/// let rows = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+/// assert_eq!(transpose!(rows), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+/// ```
+#[macro_export]
+macro_rules! transpose {
+    ($rows:expr $(,)?) => {
+        $crate::helpers::transpose($rows)
+    };
+}
+
+/// Renders a `HashMap` as a canonical `"{k1: v1, k2: v2}"` string with sorted keys.
+///
+/// The output is stable regardless of the map's iteration order, which makes it handy for
+/// snapshot tests. Keys must implement `Ord` and `Display`, and values `Display`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, sorted_map_string};
+///
+/// let map = map!("b" => 2, "a" => 1, "c" => 3);
+/// assert_eq!(sorted_map_string!(map), "{a: 1, b: 2, c: 3}");
+/// assert_eq!(sorted_map_string!(std::collections::HashMap::<u8, u8>::new()), "{}");
+/// ```
+#[macro_export]
+macro_rules! sorted_map_string {
+    ($map:expr $(,)?) => {
+        {
+            let entries: Vec<String> = $crate::helpers::sorted_entries(&$map)
+                .into_iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    };
+}
+
+/// Creates a `RangeMap` that looks values up by the range they fall in.
+///
+/// Each entry pairs a half-open range (`start..end`) with a value. `get` returns the value of
+/// the first range containing the lookup, or `None` when no range does.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::range_map;
+///
+/// let buckets = range_map!(0..10 => "low", 10..20 => "high");
+/// assert_eq!(buckets.get(&3), Some(&"low"));
+/// assert_eq!(buckets.get(&10), Some(&"high"));
+/// assert_eq!(buckets.get(&19), Some(&"high"));
+/// assert_eq!(buckets.get(&20), None); // Outside every range
+/// ```
+#[macro_export]
+macro_rules! range_map {
+    ($($range:expr => $value:expr),* $(,)?) => {
+        {
+            let mut ranges = $crate::helpers::RangeMap::new();
+            $(
+                ranges.insert($range, $value);
+            )*
+            ranges
+        }
+    };
+}
+
+/// Caches the result of a pure computation per key.
+///
+/// The closure only runs the first time a key is seen, and later calls with the same key return
+/// a clone of the cached result.
+/// - Every `memoize!()` expansion has its own cache, including several expanded from one macro.
+/// - In a generic function, every instantiation has its own cache.
+/// - Caches are thread-local: every thread computes and stores its own values.
+/// - Cached values are kept for the life of the thread and never evicted.
+/// - Keys must be `Hash + Eq + 'static` and values `Clone + 'static`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::memoize;
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let square = |n: u64| memoize!(n, || {
+///     calls.set(calls.get() + 1);
+///     n * n
+/// });
+///
+/// assert_eq!(square(4), 16);
+/// assert_eq!(square(4), 16);
+/// assert_eq!(square(5), 25);
+/// assert_eq!(calls.get(), 2); // The repeated key was served from the cache
+///
+/// fn size<T>() -> usize {
+///     memoize!((), || std::mem::size_of::<T>())
+/// }
+/// assert_eq!(size::<u8>(), 1);
+/// assert_eq!(size::<u64>(), 8);
+///
+/// macro_rules! pair {
+///     () => { (memoize!(1u8, || 10u32), memoize!(1u8, || 20u32)) };
+/// }
+/// assert_eq!(pair!(), (10, 20));
+/// ```
+#[macro_export]
+macro_rules! memoize {
+    ($key:expr, $compute:expr $(,)?) => {
+        {
+            static SITE: u8 = 0;
+            $crate::helpers::memoize(&SITE, $key, $compute)
+        }
+    };
+}
+
+/// Maps each item to a value and folds the values together with a reducer.
+///
+/// Takes the items, a mapping closure, the initial accumulator, and a reducer combining the
+/// accumulator with each mapped value. Works with anything that can be turned into an iterator.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::map_reduce;
+///
+/// struct Order { value: u32 }
+///
+/// let orders = vec![Order { value: 5 }, Order { value: 10 }, Order { value: 20 }];
+/// let total = map_reduce!(&orders, |order| order.value, 0, |a, b| a + b);
+/// assert_eq!(total, 35);
+///
+/// let largest = map_reduce!(orders, |order| order.value, 0, u32::max);
+/// assert_eq!(largest, 20);
+/// ```
+#[macro_export]
+macro_rules! map_reduce {
+    ($items:expr, $map:expr, $init:expr, $reduce:expr $(,)?) => {
+        $items.into_iter().map($map).fold($init, $reduce)
+    };
+}
+
+/// Asserts that a collection has the expected length.
+///
+/// Works with anything implementing `simplicio::Len`, which covers the standard
+/// collections, strings, slices and arrays. On failure the panic message shows both the expected
+/// and the actual length.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::assert_len;
+///
+/// assert_len!(vec![1, 2, 3], 3);
+/// assert_len!("abc", 3);
+/// assert_len!(std::collections::HashMap::<u8, u8>::new(), 0);
+/// ```
+///
+/// ```should_panic
+/// use simplicio::assert_len;
+///
+/// assert_len!(vec![1, 2], 3); // Panics: `vec![1, 2]` has length 2, expected 3
+/// ```
+#[macro_export]
+macro_rules! assert_len {
+    ($collection:expr, $expected:expr $(,)?) => {
+        {
+            let actual = $crate::helpers::Len::len(&$collection);
+            let expected: usize = $expected;
+            if actual != expected {
+                panic!(
+                    "assertion failed: `{}` has length {}, expected {}",
+                    stringify!($collection),
+                    actual,
+                    expected
+                );
+            }
+        }
+    };
+}
+
+/// Applies a closure to every sliding window of a slice, collecting the results into a `Vec`.
+///
+/// Wraps `slice::windows`, so each window is a `&[T]` of the given size and there are
+/// `len - size + 1` of them, or none when the slice is shorter than the window.
+///
+/// # Panics
+///
+/// Panics if the window size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::window_map;
+///
+/// let values = vec![1, 2, 3, 4];
+/// assert_eq!(window_map!(values, 2, |w| w[0] + w[1]), vec![3, 5, 7]);
+/// assert_eq!(window_map!(values, 5, |w| w.len()), Vec::<usize>::new());
+/// ```
+#[macro_export]
+macro_rules! window_map {
+    ($slice:expr, $size:expr, $f:expr $(,)?) => {
+        {
+            let size: usize = $size;
+            assert!(size > 0, "window_map!: window size must be greater than zero");
+            $slice.windows(size).map($f).collect::<Vec<_>>()
+        }
+    };
+}
+
+/// Builds a string like `s!()`, returning `None` when the result is empty.
+///
+/// Accepts everything `s!()` does. The result is `Some(String)` unless every fragment was empty.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::nonempty_s;
+///
+/// let (first, last) = ("Ada", "Lovelace");
+/// assert_eq!(nonempty_s!(.first, last), Some(String::from("Ada Lovelace")));
+///
+/// let (a, b, c) = ("", String::new(), "");
+/// assert_eq!(nonempty_s!(a, b, c), None);
+/// ```
+#[macro_export]
+macro_rules! nonempty_s {
+    ($($args:tt)*) => {
+        {
+            let string: String = $crate::s!($($args)*);
+            if string.is_empty() { None } else { Some(string) }
+        }
+    };
+}
+
+/// Splits a `HashMap` into a `Vec` of the given number of smaller `HashMap`s.
+///
+/// Entries are distributed in sorted key order, in contiguous runs, so the same map always
+/// splits the same way. The sub-maps differ in size by at most one entry. The map is consumed.
+///
+/// # Panics
+///
+/// Panics if the number of chunks is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_chunks};
+///
+/// let map = map!("a" => 1, "b" => 2, "c" => 3, "d" => 4, "e" => 5, "f" => 6);
+/// let shards = map_chunks!(map, 3);
+/// assert_eq!(shards, vec![
+///     map!("a" => 1, "b" => 2),
+///     map!("c" => 3, "d" => 4),
+///     map!("e" => 5, "f" => 6),
+/// ]);
+/// ```
 #[macro_export]
-macro_rules! s {
-    //Default input
-    ($($e:expr),* $(,)?) => {
-        {
-            let mut string: String = String::new();
-            $(
-                let add: &str = &$e.to_string();
-                string.push_str(add);
-            )*
-            string
-        }
+macro_rules! map_chunks {
+    ($map:expr, $chunks:expr $(,)?) => {
+        $crate::helpers::map_chunks($map, $chunks)
     };
+}
 
-    //Automatic spacing concatination
-    (.$($e:expr),* $(,)?) => {
-        {
-            let mut string: String = String::new();
-            let mut first = true;
-            $(
-                if !first { string.push(' '); } else { first = false; }
-                let add: &str = &$e.to_string();
-                string.push_str(add);
-            )*
-            string
-        }
+/// Serializes a `HashMap` as a JSON object `String`, without any dependencies.
+///
+/// Keys are stringified and quoted. Values are rendered through
+/// `simplicio::JsonValue`: strings are quoted and escaped, numbers and booleans are
+/// written bare, and `None` becomes `null`. Entries are sorted by key, so the output is stable.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_to_json};
+///
+/// let names = map!("greeting" => "say \"hi\"", "name" => "Ada");
+/// assert_eq!(map_to_json!(names), r#"{"greeting":"say \"hi\"","name":"Ada"}"#);
+///
+/// let numbers = map!("ratio" => 2.5, "zero" => 0.0);
+/// assert_eq!(map_to_json!(numbers), r#"{"ratio":2.5,"zero":0}"#);
+/// ```
+#[macro_export]
+macro_rules! map_to_json {
+    ($map:expr $(,)?) => {
+        $crate::helpers::map_to_json(&$map)
     };
 }
 
-/// Concatenates multiple string slices and/or `String` objects.
+/// Calls a producer until its result satisfies a predicate or the maximum attempts are used up.
 ///
-/// A wrapper for the `s!()` macro that allows people to assign purpose to one for ease of readability.
+/// Returns the first result that passed the predicate, or the last result otherwise. The
+/// predicate receives a reference to each result.
+///
+/// # Panics
+///
+/// Panics if `max` is zero.
 ///
 /// # Examples
 ///
-/// ```no_run
-/// use simplicio::cnct;
+/// ```
+/// use simplicio::retry_until;
 ///
-/// // Creating an enum for example purposes
-/// enum Enum { Value }  //Create the enum, Enum
-/// impl std::fmt::Display for Enum {  // Implement the Display trait
-///    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-///        match self { Enum::Value => write!(f, "value"), }
-///    }
-/// }
+/// let mut attempts = 0;
+/// let fetch = || {
+///     attempts += 1;
+///     if attempts < 3 { Err("not yet") } else { Ok(attempts) }
+/// };
 ///
-/// let concatenated_string = cnct!("Yel", "low", "!");
-/// assert_eq!(concatenated_string, String::from("Yellow!"));
+/// assert_eq!(retry_until!(max = 5, fetch, |r| r.is_ok()), Ok(3));
+/// assert_eq!(attempts, 3);
 ///
-/// let text = String::from("this");
-/// let concat_string = cnct!(.text, true, 123, Enum::Value); //Assuming Enum implements the ToString trait and is set to "Value"
-/// assert_eq!(concat_string, String::from("this true 123 value"));
+/// let gave_up = retry_until!(max = 2, || Err::<u8, _>("down"), |r| r.is_ok());
+/// assert_eq!(gave_up, Err("down"));
 /// ```
 #[macro_export]
-macro_rules! cnct {
-    //Default case
-    ($($e:expr),* $(,)?) => {
-        $crate::s!($($e),*);
+macro_rules! retry_until {
+    (max = $max:expr, $producer:expr, $predicate:expr $(,)?) => {
+        $crate::helpers::retry_until($max, $producer, $predicate)
     };
+}
 
-    //Automatic spacing
-    (.$($e:expr),* $(,)?) => {
-        $crate::s!(.$($e),*);
+/// Merges the entries of one map into another in place.
+///
+/// By default the source's value wins whenever a key exists in both. Passing a resolver closure
+/// as the third argument calls it with the old and new value of each conflicting key, and stores
+/// what it returns. The source can be anything that iterates over key-value pairs.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_merge_assign};
+///
+/// let mut config = map!("host" => "localhost", "port" => "8080");
+/// map_merge_assign!(config, map!("port" => "9090", "tls" => "on"));
+/// assert_eq!(config, map!("host" => "localhost", "port" => "9090", "tls" => "on"));
+///
+/// let mut counts = map!("apples" => 2, "pears" => 1);
+/// map_merge_assign!(counts, map!("apples" => 3, "plums" => 4), |old, new| old + new);
+/// assert_eq!(counts, map!("apples" => 5, "pears" => 1, "plums" => 4));
+/// ```
+#[macro_export]
+macro_rules! map_merge_assign {
+    ($target:expr, $source:expr $(,)?) => {
+        $target.extend($source)
+    };
+    ($target:expr, $source:expr, $resolve:expr $(,)?) => {
+        $crate::helpers::merge_with(&mut $target, $source, $resolve)
     };
 }
 
-/// Creates a `HashMap` from a list of key-value pairs.
+/// Asserts that a `HashMap` contains every listed key, in debug builds only.
 ///
-/// This macro simplifies the creation of a `HashMap` from a series
-/// of key-value pairs. It's a convenient way to initialize a `HashMap`
-/// without manually calling `insert` for each pair.
+/// Like `debug_assert!`, the check is skipped when debug assertions are disabled, as in release
+/// builds. Keys are given in the borrowed form accepted by `HashMap::contains_key`, so string
+/// literals work for both `&str` and `String` keys, while other key types are passed by reference
+/// (e.g. `&1`). On failure the panic message lists every missing key, so the key type must
+/// implement `Debug`.
 ///
 /// # Examples
 ///
-/// ```no_run
-/// use simplicio::map;
-/// 
-/// // Create the HashMap test_map = {"k1": "v1", "k2": "v2"}
-/// let mut test_map = std::collections::HashMap::new(); 
-/// test_map.insert("k1", "v1");
-/// test_map.insert("k2", "v2");
+/// ```
+/// use simplicio::{debug_assert_keys, map};
+/// use std::collections::HashMap;
 ///
-/// let mut hashmaps: Vec<std::collections::HashMap<&str, &str>> = vec![]; // Holds all variants of map!()
-/// let vecmap = vec![("k1", "v1"), ("k2", "v2")]; // Vector of key/value tuples
-/// let arrmap = [("k1", "v1"), ("k2", "v2")]; // Array of key/value tuples
-/// hashmaps.push( map!(vecmap) );  // Can insert a vector
-/// hashmaps.push( map!(arrmap) );  // Can insert an array
-/// hashmaps.push( map!("k1" "v1", "k2" "v2") );     // Direct insert: uses `' '` 
-/// hashmaps.push( map!("k1": "v1", "k2": "v2") );   // Direct insert: uses `:` 
-/// hashmaps.push( map!("k1"->"v1", "k2"->"v2") );   // Direct insert: uses `->` 
-/// hashmaps.push( map!("k1"=>"v1", "k2"=>"v2") );   // Direct insert: uses `=>` 
-/// hashmaps.push( map!("k1"["v1"], "k2"["v2"]) ); // Direct insert: uses `[]` to annotate a value
+/// let config = map!("host" => "localhost", "port" => "8080");
+/// debug_assert_keys!(config, "host", "port");
 ///
-/// assert!(hashmaps.iter().all(|map| map == &test_map)); // Assert that all cases are true
+/// let mut owned = HashMap::new();
+/// owned.insert(String::from("host"), String::from("localhost"));
+/// owned.insert(String::from("port"), String::from("8080"));
+/// debug_assert_keys!(owned, "host", "port");
+///
+/// let ids = map!(1 => "one", 2 => "two");
+/// debug_assert_keys!(ids, &1, &2);
+/// ```
+///
+/// ```should_panic
+/// use simplicio::{debug_assert_keys, map};
+///
+/// let config = map!("host" => "localhost");
+/// debug_assert_keys!(config, "host", "port", "user"); // Panics: missing ["port", "user"]
 /// ```
 #[macro_export]
-macro_rules! map {
-    // For new
-    () => { std::collections::HashMap::new() };
+macro_rules! debug_assert_keys {
+    ($map:expr, $($key:expr),+ $(,)?) => {
+        if cfg!(debug_assertions) {
+            let missing = $crate::helpers::missing_keys(&$map, &[$($key),+]);
+            if !missing.is_empty() {
+                panic!("assertion failed: `{}` is missing keys {:?}", stringify!($map), missing);
+            }
+        }
+    };
+}
 
-    // key[value]
-    ($($key:path[$val:expr]),+ $(,)?) => { map!(@mapper $($key, $val),+) };
-    ($($key:tt[$val:expr]),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+/// Maps every item to an iterable and flattens the results into a single `Vec`.
+///
+/// Works with anything that can be turned into an iterator, as long as the closure returns
+/// something iterable, such as a `Vec`, an array or an `Option`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::flat_map_vec;
+///
+/// assert_eq!(flat_map_vec!(vec![1, 2, 3], |x| vec![x, x]), vec![1, 1, 2, 2, 3, 3]);
+/// assert_eq!(flat_map_vec!(["a b", "c"], |s| s.split(' ')), vec!["a", "b", "c"]);
+/// ```
+#[macro_export]
+macro_rules! flat_map_vec {
+    ($items:expr, $f:expr $(,)?) => {
+        $items.into_iter().flat_map($f).collect::<Vec<_>>()
+    };
+}
 
-    // key value || key: value || key -> value || key => value
-    ($($key:tt$(:)?$(->)?$(=>)?$val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+/// Collects the values produced by a closure into a `HashSet`.
+///
+/// Works with anything that can be turned into an iterator, and duplicates are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::collect_set;
+///
+/// struct Product { name: &'static str, category: &'static str }
+///
+/// let products = vec![
+///     Product { name: "apple", category: "fruit" },
+///     Product { name: "carrot", category: "vegetable" },
+///     Product { name: "pear", category: "fruit" },
+/// ];
+///
+/// let categories = collect_set!(&products, |p| p.category);
+/// assert_eq!(categories.len(), 2);
+/// assert!(categories.contains("fruit") && categories.contains("vegetable"));
+///
+/// let names = collect_set!(products, |p| p.name);
+/// assert!(names.contains("carrot"));
+/// ```
+#[macro_export]
+macro_rules! collect_set {
+    ($items:expr, $f:expr $(,)?) => {
+        $items.into_iter().map($f).collect::<std::collections::HashSet<_>>()
+    };
+}
 
-    // When keys are paths and not tokens
-    ($($key:path : $val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
-    ($($key:path => $val:expr),+ $(,)?) => { map!(@mapper $($key, $val),+) };
+/// Returns the part of the first `HashMap` whose keys are also in the second.
+///
+/// The keys and values are cloned from the first map, and the second map's values are ignored,
+/// so its value type can differ.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_intersect};
+///
+/// let prices = map!("apple" => 3, "pear" => 4, "fig" => 9);
+/// let in_stock = map!("pear" => true, "fig" => false, "kiwi" => true);
+/// assert_eq!(map_intersect!(prices, in_stock), map!("pear" => 4, "fig" => 9));
+///
+/// let none = map!("plum" => true);
+/// assert!(map_intersect!(prices, none).is_empty());
+/// ```
+#[macro_export]
+macro_rules! map_intersect {
+    ($a:expr, $b:expr $(,)?) => {
+        {
+            let other = &$b;
+            $a.iter()
+                .filter(|(key, _)| other.contains_key(*key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<std::collections::HashMap<_, _>>()
+        }
+    };
+}
 
-    // Vec<(_,_)>, &[(_,_)], or manually input map!([(k,v)])
-    ($($arr:expr),+ $(,)?) => {
+/// Runs a closure for every entry of a `HashMap`, for its side effects.
+///
+/// The closure receives a reference to each key and value, in the map's iteration order. The map
+/// is only borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, map_apply};
+///
+/// let env = map!("HOME" => "/root", "LANG" => "C");
+/// map_apply!(env, |k, v| println!("{}={}", k, v));
+///
+/// let mut seen = Vec::new();
+/// map_apply!(env, |k, v| seen.push(format!("{}={}", k, v)));
+/// seen.sort();
+/// assert_eq!(seen, vec!["HOME=/root", "LANG=C"]);
+/// ```
+#[macro_export]
+macro_rules! map_apply {
+    ($map:expr, $f:expr $(,)?) => {
+        $crate::helpers::for_each_entry(&$map, $f)
+    };
+}
+
+/// Builds a `Vec` from a list of pushes, some of which can be guarded by a condition.
+///
+/// - `push value` always adds the value.
+/// - `if condition => push value` only adds the value when the condition is `true`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::build_vec;
+///
+/// let verbose = true;
+/// assert_eq!(build_vec!(push 1, if verbose => push 2, push 3), vec![1, 2, 3]);
+///
+/// let verbose = false;
+/// assert_eq!(build_vec!(push 1, if verbose => push 2, push 3), vec![1, 3]);
+///
+/// let args = build_vec!(push "run", if !verbose => push "--quiet");
+/// assert_eq!(args, vec!["run", "--quiet"]);
+/// ```
+#[macro_export]
+macro_rules! build_vec {
+    //Pushes each element in turn
+    (@push $vec:ident;) => {};
+    (@push $vec:ident; if $cond:expr => push $e:expr $(, $($rest:tt)*)?) => {
+        if $cond {
+            $vec.push($e);
+        }
+        $crate::build_vec!(@push $vec; $($($rest)*)?);
+    };
+    (@push $vec:ident; push $e:expr $(, $($rest:tt)*)?) => {
+        $vec.push($e);
+        $crate::build_vec!(@push $vec; $($($rest)*)?);
+    };
+
+    ($($elements:tt)*) => {
         {
-            let mut map = map!();
-            $(
-                map.extend($crate::helpers::mapper($arr));
-            )+
-            map 
+            let mut vec = Vec::new();
+            $crate::build_vec!(@push vec; $($elements)*);
+            vec
         }
     };
+}
 
-    //Does the bulk of the mapping
-    (@mapper $($key:expr, $value:expr),+ $(,)?) => {
+/// Collects the values of a `HashMap` into a `Vec`, ordered by their sorted keys.
+///
+/// The values line up index-for-index with the map's keys in sorted order, which keeps parallel
+/// key and value arrays aligned. Keys must implement `Ord` and values `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::{map, values_by_key};
+///
+/// let ages = map!("cid" => 41, "ann" => 29, "bob" => 35);
+/// let mut keys: Vec<_> = ages.keys().copied().collect();
+/// keys.sort();
+///
+/// assert_eq!(keys, vec!["ann", "bob", "cid"]);
+/// assert_eq!(values_by_key!(ages), vec![29, 35, 41]);
+/// ```
+#[macro_export]
+macro_rules! values_by_key {
+    ($map:expr $(,)?) => {
+        $crate::helpers::sorted_entries(&$map)
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<_>>()
+    };
+}
+
+/// Runs one of two closures depending on whether a `Result` is `Ok` or `Err`.
+///
+/// The first closure receives the `Ok` value and the second the `Err` value. Both must return
+/// the same type, which becomes the value of the macro.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::either;
+///
+/// let ok: Result<u32, String> = Ok(42);
+/// assert_eq!(either!(ok, |n| format!("got {}", n), |e| format!("failed: {}", e)), "got 42");
+///
+/// let err: Result<u32, String> = Err(String::from("timeout"));
+/// assert_eq!(either!(err, |n| format!("got {}", n), |e| format!("failed: {}", e)), "failed: timeout");
+/// ```
+#[macro_export]
+macro_rules! either {
+    ($result:expr, $on_ok:expr, $on_err:expr $(,)?) => {
+        $crate::helpers::either($result, $on_ok, $on_err)
+    };
+}
+
+/// Collects the environment variables starting with a prefix into a `HashMap<String, String>`.
+///
+/// The prefix is stripped from the keys. Like `std::env::vars`, this panics if any environment
+/// variable's name or value is not valid Unicode.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::env_prefix_map;
+///
+/// std::env::set_var("SIMPLICIO_DOC_HOST", "localhost");
+/// std::env::set_var("SIMPLICIO_DOC_PORT", "8080");
+///
+/// let config = env_prefix_map!("SIMPLICIO_DOC_");
+/// assert_eq!(config.len(), 2);
+/// assert_eq!(config["HOST"], "localhost");
+/// assert_eq!(config["PORT"], "8080");
+/// ```
+#[macro_export]
+macro_rules! env_prefix_map {
+    ($prefix:expr $(,)?) => {
         {
-            let mut map = std::collections::HashMap::new();
-            $(
-                map.insert($key, $value);
-            )+
-            map
+            let prefix: &str = &$prefix.to_string();
+            std::env::vars()
+                .filter_map(|(key, value)| key.strip_prefix(prefix).map(|key| (key.to_string(), value)))
+                .collect::<std::collections::HashMap<String, String>>()
         }
-    }
+    };
 }
 
-/// Prints to the console with a newline.
-/// Convenient shorthand for `println!`.
+/// Joins two `HashMap`s on their keys, pairing up the values of every key found in both.
+///
+/// Keys found in only one of the maps are left out. The keys and values are cloned, so both maps
+/// are only borrowed.
 ///
 /// # Examples
 ///
-/// Basic usage:
+/// ```
+/// use simplicio::{map, map_zip};
+///
+/// let prices = map!("apple" => 3, "pear" => 4, "fig" => 9);
+/// let stock = map!("pear" => 12, "fig" => 0, "kiwi" => 5);
+/// assert_eq!(map_zip!(prices, stock), map!("pear" => (4, 12), "fig" => (9, 0)));
+/// ```
+#[macro_export]
+macro_rules! map_zip {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::helpers::zip_values(&$a, &$b)
+    };
+}
+
+/// Splits a string into groups of a fixed number of characters, joined by a separator.
+///
+/// Handy for formatting card numbers, hashes or other long codes. Groups are counted in
+/// characters, not bytes, and the last group may be shorter than the rest.
+///
+/// # Panics
+///
+/// Panics if the group size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use simplicio::chunked_string;
+///
+/// assert_eq!(chunked_string!("1234567890123456", 4, " "), "1234 5678 9012 3456"); // Evenly divisible
+/// assert_eq!(chunked_string!("1234567890", 4, " "), "1234 5678 90"); // Shorter last group
+/// assert_eq!(chunked_string!("äöüß", 2, "-"), "äö-üß"); // Counts characters, not bytes
+/// ```
+#[macro_export]
+macro_rules! chunked_string {
+    ($text:expr, $size:expr, $separator:expr $(,)?) => {
+        $crate::helpers::chunk_string(&$text.to_string(), $size, &$separator.to_string())
+    };
+}
+
+/// Describes the differences between two `HashMap`s as human-readable text.
+///
+/// Produces one line per difference: first the added keys, then the removed keys, then the
+/// changed keys with their old and new values, each group sorted by key. Identical maps give an
+/// empty string. Keys must implement `Ord` and `Display`, and values `Display` and `PartialEq`.
+///
+/// # Examples
 ///
 /// ```
-/// # use logfather::n;
-/// n!();
+/// use simplicio::{map, map_diff_string};
+///
+/// let old = map!("host" => "localhost", "port" => "8080", "debug" => "on");
+/// let new = map!("host" => "localhost", "port" => "9090", "user" => "admin");
+/// assert_eq!(
+///     map_diff_string!(old, new),
+///     "+ added: user\n- removed: debug\n~ changed: port (8080 -> 9090)"
+/// );
+/// assert_eq!(map_diff_string!(old, old), "");
 /// ```
 #[macro_export]
-macro_rules! ln {
-    () => { println!() };
+macro_rules! map_diff_string {
+    ($old:expr, $new:expr $(,)?) => {
+        {
+            let diff = $crate::helpers::map_diff(&$old, &$new);
+            let mut lines: Vec<String> = Vec::new();
+            lines.extend(diff.added.iter().map(|key| format!("+ added: {}", key)));
+            lines.extend(diff.removed.iter().map(|key| format!("- removed: {}", key)));
+            lines.extend(
+                diff.changed
+                    .iter()
+                    .map(|(key, old, new)| format!("~ changed: {} ({} -> {})", key, old, new)),
+            );
+            lines.join("\n")
+        }
+    };
 }